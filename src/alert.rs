@@ -1,7 +1,8 @@
-use crate::ProcessingReceipt;
+use crate::{IncidentGuard, ProcessingReceipt};
 use serde_json::json;
 use std::panic::PanicHookInfo;
 
+#[derive(Clone)]
 pub struct Alert {
     id: u64,
     meta: AlertMeta,
@@ -28,8 +29,20 @@ impl Alert {
         &self.meta.dedup_key
     }
 
+    pub fn action(&self) -> Action {
+        self.meta.action
+    }
+
+    /// Returns the location in the source code the alert originated from. Known for alerts built from panics, and
+    /// from errors reported through [AirbagResult](crate::AirbagResult)
+    pub fn location(&self) -> Option<&SourceLocation> {
+        self.meta.location.as_ref()
+    }
+
+    #[track_caller]
     pub(crate) fn build_error_alert<E: std::fmt::Debug + 'static>(e: &E) -> AlertBuilder {
         let mut returned = Self::builder();
+        returned.meta.location = Some(std::panic::Location::caller().into());
         let e_any: &dyn std::any::Any = e;
         let e_dbg = format!("{:?}", e);
 
@@ -37,8 +50,20 @@ impl Alert {
 
         if let Some(e) = e_any.downcast_ref::<anyhow::Error>() {
             returned = returned.title(e.to_string()).description(&e_dbg);
+            returned.meta.error = Some(Box::new(ErrorInfo {
+                type_name: std::any::type_name::<E>().to_owned(),
+                message: e.to_string(),
+                causes: e.chain().skip(1).map(ToString::to_string).collect(),
+                backtrace: AlertBacktrace::of_error(e),
+            }));
         } else {
             returned = returned.title(&e_dbg);
+            returned.meta.error = Some(Box::new(ErrorInfo {
+                type_name: std::any::type_name::<E>().to_owned(),
+                message: e_dbg.clone(),
+                causes: Vec::new(),
+                backtrace: AlertBacktrace::capture(),
+            }));
         }
 
         if returned.meta.dedup_key.is_none() {
@@ -65,10 +90,33 @@ impl Alert {
 
         let dedup_key = crate::utils::sha256(&summary);
 
-        Self::builder()
+        let mut returned = Self::builder()
             .title(summary.clone())
             .description(summary)
-            .dedup_key(dedup_key)
+            .dedup_key(dedup_key);
+        returned.meta.error = Some(Box::new(ErrorInfo {
+            type_name: "panic".to_owned(),
+            message: info
+                .payload()
+                .downcast_ref::<String>()
+                .map(String::as_str)
+                .or_else(|| info.payload().downcast_ref::<&str>().copied())
+                .unwrap_or("N/A")
+                .to_owned(),
+            causes: Vec::new(),
+            backtrace: AlertBacktrace::capture(),
+        }));
+        returned.meta.location = info.location().map(Into::into);
+        returned
+    }
+
+    /// Rebuilds an alert from its metadata and fields, e.g. when restoring a persisted alert
+    pub(crate) fn from_parts(meta: AlertMeta, value: serde_json::Value) -> Self {
+        Self {
+            id: ALERT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+            meta,
+            value,
+        }
     }
 
     pub(crate) fn as_json(&self) -> &serde_json::Value {
@@ -83,6 +131,10 @@ impl Alert {
         &self.meta
     }
 
+    pub(crate) fn meta_mut(&mut self) -> &mut AlertMeta {
+        &mut self.meta
+    }
+
     pub fn get_field(&self, name: impl AsRef<str>) -> Option<&serde_json::Value> {
         self.value.get(name.as_ref())
     }
@@ -115,7 +167,7 @@ impl Alert {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
 pub enum Severity {
     Critical,
     Error,
@@ -123,6 +175,18 @@ pub enum Severity {
     Info,
 }
 
+impl Severity {
+    /// The lowercase name of the severity, e.g. `"critical"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Critical => "critical",
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+        }
+    }
+}
+
 impl From<Severity> for Priority {
     fn from(value: Severity) -> Self {
         match value {
@@ -134,7 +198,7 @@ impl From<Severity> for Priority {
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
 pub enum Priority {
     P1,
     P2,
@@ -143,6 +207,19 @@ pub enum Priority {
     P5,
 }
 
+impl Priority {
+    /// The name of the priority, e.g. `"P1"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Priority::P1 => "P1",
+            Priority::P2 => "P2",
+            Priority::P3 => "P3",
+            Priority::P4 => "P4",
+            Priority::P5 => "P5",
+        }
+    }
+}
+
 impl From<Priority> for Severity {
     fn from(value: Priority) -> Self {
         match value {
@@ -154,13 +231,130 @@ impl From<Priority> for Severity {
     }
 }
 
-#[derive(Default)]
+/// The lifecycle action an alert represents. Alerts trigger new incidents by default, but can also be used to
+/// acknowledge or resolve an incident previously triggered with the same dedup key
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Action {
+    #[default]
+    Trigger,
+    Acknowledge,
+    Resolve,
+}
+
+impl Action {
+    /// The lowercase name of the action, e.g. `"resolve"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Action::Trigger => "trigger",
+            Action::Acknowledge => "acknowledge",
+            Action::Resolve => "resolve",
+        }
+    }
+}
+
+#[derive(Clone, Default, serde::Serialize, serde::Deserialize)]
 pub(crate) struct AlertMeta {
     pub(crate) title: Option<String>,
     pub(crate) description: Option<String>,
     pub(crate) dedup_key: Option<String>,
     pub(crate) severity: Option<Severity>,
     pub(crate) priority: Option<Priority>,
+    pub(crate) action: Action,
+    pub(crate) dedup_window: Option<std::time::Duration>,
+    pub(crate) error: Option<Box<ErrorInfo>>,
+    pub(crate) location: Option<SourceLocation>,
+}
+
+impl AlertMeta {
+    /// The alert's severity, derived from its priority if it has none, and [Severity::Error] if it has neither
+    pub(crate) fn effective_severity(&self) -> Severity {
+        self.severity
+            .unwrap_or_else(|| self.priority.map(Into::into).unwrap_or(Severity::Error))
+    }
+
+    /// The alert's priority, derived from its severity if it has none, and from [Severity::Error] if it has neither
+    pub(crate) fn effective_priority(&self) -> Priority {
+        self.priority
+            .unwrap_or_else(|| self.severity.unwrap_or(Severity::Error).into())
+    }
+}
+
+/// Details of the error or panic an alert was built from, for backends which track errors rather than incidents
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub(crate) struct ErrorInfo {
+    pub(crate) type_name: String,
+    pub(crate) message: String,
+    /// The messages of the error's sources, outermost first
+    pub(crate) causes: Vec<String>,
+    pub(crate) backtrace: Option<AlertBacktrace>,
+}
+
+/// A backtrace captured where an alert was built. Symbolizing a backtrace is expensive, so it is kept unresolved until
+/// a backend formats it. Persisted alerts carry the formatted backtrace instead
+#[derive(Clone, Debug)]
+pub(crate) enum AlertBacktrace {
+    Captured(std::sync::Arc<std::backtrace::Backtrace>),
+    Formatted(String),
+}
+
+impl AlertBacktrace {
+    fn capture() -> Option<Self> {
+        let backtrace = std::backtrace::Backtrace::capture();
+        (backtrace.status() == std::backtrace::BacktraceStatus::Captured)
+            .then(|| Self::Captured(std::sync::Arc::new(backtrace)))
+    }
+
+    /// Uses the backtrace captured where the error was created, rather than where it was reported, if it has one.
+    /// The error's backtrace can only be borrowed, so it is formatted right away
+    fn of_error(e: &anyhow::Error) -> Option<Self> {
+        let backtrace = e.backtrace();
+        match backtrace.status() {
+            std::backtrace::BacktraceStatus::Captured => {
+                Some(Self::Formatted(backtrace.to_string()))
+            }
+            _ => Self::capture(),
+        }
+    }
+}
+
+impl std::fmt::Display for AlertBacktrace {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Captured(backtrace) => backtrace.fmt(f),
+            Self::Formatted(backtrace) => f.write_str(backtrace),
+        }
+    }
+}
+
+impl serde::Serialize for AlertBacktrace {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for AlertBacktrace {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::Formatted)
+    }
+}
+
+/// A location in the source code, as reported by [std::panic::Location]
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SourceLocation {
+    /// The path of the source file, relative to the workspace root for the application's own files
+    pub file: String,
+    pub line: u32,
+    pub column: u32,
+}
+
+impl From<&std::panic::Location<'_>> for SourceLocation {
+    fn from(location: &std::panic::Location<'_>) -> Self {
+        Self {
+            file: location.file().to_owned(),
+            line: location.line(),
+            column: location.column(),
+        }
+    }
 }
 
 pub struct AlertBuilder {
@@ -200,6 +394,29 @@ impl AlertBuilder {
         self
     }
 
+    /// Sets the lifecycle action of the alert. Acknowledging or resolving requires a dedup key matching the one
+    /// used when triggering the incident
+    ///
+    /// ```
+    /// use airbag::alert::{Action, Alert};
+    ///
+    /// Alert::builder()
+    ///     .dedup_key("db-fallback")
+    ///     .action(Action::Resolve)
+    ///     .trigger();
+    /// ```
+    pub fn action(mut self, action: Action) -> Self {
+        self.meta.action = action;
+        self
+    }
+
+    /// Overrides the hub's dedup window for this alert. A zero duration disables local suppression of this alert
+    /// altogether
+    pub fn dedup_window(mut self, window: std::time::Duration) -> Self {
+        self.meta.dedup_window.replace(window);
+        self
+    }
+
     pub fn build(self) -> Alert {
         self.into()
     }
@@ -207,6 +424,19 @@ impl AlertBuilder {
     pub fn trigger(self) -> ProcessingReceipt {
         crate::hub::trigger(self.build())
     }
+
+    /// Triggers the alert, returning a guard that resolves the incident when dropped or closed. Alerts without a
+    /// dedup key are assigned one, as it is needed to tie the resolution to the triggered incident
+    pub fn trigger_guarded(mut self) -> IncidentGuard {
+        if self.meta.dedup_key.is_none() {
+            let dedup_key = match &self.meta.title {
+                Some(title) => crate::utils::sha256(title),
+                None => format!("airbag-incident-{}", self.id),
+            };
+            self = self.dedup_key(dedup_key);
+        }
+        IncidentGuard::trigger(self.build())
+    }
 }
 
 impl From<AlertBuilder> for Alert {
@@ -276,7 +506,6 @@ pub(crate) mod middleware {
     ///   airbag::middleware::DedupKeyPrefix::new("your prefix")
     /// );
     /// ```
-
     pub struct DedupKeyPrefix {
         prefix: String,
     }
@@ -326,4 +555,43 @@ mod tests {
             .with_field_if_missing("x", "y");
         assert_eq!(alert.get_field("x").unwrap().as_str().unwrap(), "1");
     }
+
+    #[test]
+    fn test_action_defaults_to_trigger() {
+        let alert = Alert::builder().build();
+        assert_eq!(alert.action(), super::Action::Trigger);
+
+        let alert = Alert::builder().action(super::Action::Resolve).build();
+        assert_eq!(alert.action(), super::Action::Resolve);
+    }
+
+    #[test]
+    fn test_backtrace_serialization() {
+        let backtrace = super::AlertBacktrace::Captured(std::sync::Arc::new(
+            std::backtrace::Backtrace::force_capture(),
+        ));
+
+        let serialized = serde_json::to_value(&backtrace).unwrap();
+        assert_eq!(serialized, backtrace.to_string());
+
+        let deserialized: super::AlertBacktrace = serde_json::from_value(serialized).unwrap();
+        assert!(
+            matches!(&deserialized, super::AlertBacktrace::Formatted(s) if *s == backtrace.to_string())
+        );
+    }
+
+    #[test]
+    fn test_error_backtrace_origin() {
+        let e = anyhow::anyhow!("failed");
+        let alert = Alert::build_error_alert(&e).build();
+
+        // errors carrying a backtrace are reported with it, rather than with one captured when reporting them
+        if e.backtrace().status() == std::backtrace::BacktraceStatus::Captured {
+            let error = alert.meta().error.as_ref().unwrap();
+            assert_eq!(
+                error.backtrace.as_ref().map(ToString::to_string),
+                Some(e.backtrace().to_string())
+            );
+        }
+    }
 }
@@ -0,0 +1,164 @@
+use std::{collections::HashMap, time::Instant};
+
+use anyhow::Context;
+use parking_lot::Mutex;
+use reqwest::Url;
+use serde_json::{json, Map, Value};
+
+use super::RetryPolicy;
+use crate::{
+    alert::{Action, AlertMeta, Severity},
+    http::HttpRequest,
+    utils::field_text,
+};
+
+const MAX_INCIDENTS: usize = 10_000;
+
+type Labels = Map<String, Value>;
+
+/// The `Alertmanager` struct implements a backend for the
+/// [Prometheus Alertmanager](https://prometheus.io/docs/alerting/latest/alertmanager/), posting alerts to its
+/// `/api/v2/alerts` endpoint.
+///
+/// Alerts are labeled with `alertname`, set to the alert's title, along with `severity` and `dedup_key`, in addition to
+/// any static labels configured on the backend. The title and description are sent as the `summary` and `description`
+/// annotations, along with the alert's fields.
+///
+/// Alertmanager identifies alerts by their labels, so resolving alerts send the labels of the alert triggered with the
+/// same dedup key, with `endsAt` set to the current time. Labels are remembered for the most recent alerts only, and
+/// not across restarts, so resolving alerts of unknown incidents are labeled from their own title and severity, which
+/// must then match the triggering alert's. Note that unless re-sent, triggered alerts are considered resolved by
+/// Alertmanager after its `resolve_timeout`. Acknowledging alerts is not supported, as Alertmanager expresses it with
+/// silences.
+///
+/// ```
+/// use airbag::backends::Alertmanager;
+///
+/// let backend = Alertmanager::builder()
+///     .base_url("http://alertmanager:9093")
+///     .labels(vec![("service".to_owned(), "billing".to_owned())])
+///     .build();
+/// ```
+#[derive(typed_builder::TypedBuilder)]
+pub struct Alertmanager {
+    #[builder(setter(into))]
+    base_url: String,
+
+    /// Static labels added to every alert sent through this backend
+    #[builder(default)]
+    labels: Vec<(String, String)>,
+
+    #[builder(default)]
+    retry_policy: RetryPolicy,
+
+    /// Labels of triggered alerts by their dedup key, along with when they were triggered, so that resolving alerts
+    /// carry the same labels
+    #[builder(default, setter(skip))]
+    incidents: Mutex<HashMap<String, (Labels, Instant)>>,
+}
+
+impl_http_backend!(Alertmanager);
+
+impl Alertmanager {
+    fn request(&self, alert: &crate::alert::Alert) -> anyhow::Result<HttpRequest> {
+        let url = Url::parse(&self.base_url)
+            .context("Cannot parse URL")?
+            .join("/api/v2/alerts")
+            .unwrap();
+
+        let AlertMeta {
+            title,
+            description,
+            dedup_key,
+            action,
+            ..
+        } = alert.meta();
+
+        let now = humantime::format_rfc3339_seconds(std::time::SystemTime::now()).to_string();
+        let severity = alert.meta().effective_severity();
+
+        let json = match action {
+            Action::Trigger => {
+                let labels = self.labels(title.as_deref(), severity, dedup_key.as_deref());
+                if let Some(dedup_key) = dedup_key {
+                    self.remember_incident(dedup_key.clone(), labels.clone());
+                }
+
+                let mut annotations = alert
+                    .get_fields()
+                    .as_object()
+                    .into_iter()
+                    .flatten()
+                    // annotation values must be strings
+                    .map(|(name, value)| (name.clone(), json!(field_text(value))))
+                    .collect::<Map<_, _>>();
+                if let Some(title) = title {
+                    annotations.insert("summary".into(), json!(title));
+                }
+                if let Some(description) = description {
+                    annotations.insert("description".into(), json!(description));
+                }
+
+                json!([{
+                    "labels": labels,
+                    "annotations": annotations,
+                    "startsAt": now,
+                }])
+            }
+            Action::Resolve => {
+                let dedup_key = dedup_key
+                    .as_deref()
+                    .context("Alertmanager requires a dedup key to resolve alerts")
+                    .map_err(super::permanent)?;
+                let labels = self
+                    .incidents
+                    .lock()
+                    .remove(dedup_key)
+                    .map(|(labels, _)| labels)
+                    .unwrap_or_else(|| self.labels(title.as_deref(), severity, Some(dedup_key)));
+
+                json!([{
+                    "labels": labels,
+                    "endsAt": now,
+                }])
+            }
+            Action::Acknowledge => {
+                bail_permanent!("Alertmanager does not support acknowledging alerts")
+            }
+        };
+
+        log::debug!("Alertmanager alerts: {json:?}");
+
+        log::debug!("Sending alert #{} to Alertmanager", alert.id());
+        Ok(HttpRequest::post_json(url, json))
+    }
+
+    /// Builds the labels identifying an alert, which must be the same when triggering and resolving it
+    fn labels(&self, title: Option<&str>, severity: Severity, dedup_key: Option<&str>) -> Labels {
+        let mut labels = self
+            .labels
+            .iter()
+            .map(|(name, value)| (name.clone(), json!(value)))
+            .collect::<Map<_, _>>();
+        labels.insert("alertname".into(), json!(title.unwrap_or("AirbagAlert")));
+        labels.insert("severity".into(), json!(severity.as_str()));
+        if let Some(dedup_key) = dedup_key {
+            labels.insert("dedup_key".into(), json!(dedup_key));
+        }
+        labels
+    }
+
+    fn remember_incident(&self, dedup_key: String, labels: Labels) {
+        let mut incidents = self.incidents.lock();
+        if incidents.len() >= MAX_INCIDENTS && !incidents.contains_key(&dedup_key) {
+            let oldest = incidents
+                .iter()
+                .min_by_key(|(_, (_, triggered_at))| *triggered_at)
+                .map(|(dedup_key, _)| dedup_key.clone());
+            if let Some(oldest) = oldest {
+                incidents.remove(&oldest);
+            }
+        }
+        incidents.insert(dedup_key, (labels, Instant::now()));
+    }
+}
@@ -0,0 +1,243 @@
+use std::{collections::HashMap, time::Instant};
+
+use anyhow::Context;
+use globset::{GlobBuilder, GlobMatcher};
+
+use super::Backend;
+use crate::{alert::Action, Alert};
+
+const MAX_INCIDENTS: usize = 10_000;
+
+/// The `CodeOwners` struct implements a backend delivering alerts to the team owning the code they originated from,
+/// according to a CODEOWNERS-style table mapping file patterns to owners. Each owner is given its own backend, e.g. a
+/// [PagerDuty](super::PagerDuty) backend with the team's routing key.
+///
+/// Alerts are matched by their [source location](crate::Alert::location), known for panics and for errors reported
+/// through [AirbagResult](crate::AirbagResult). As in CODEOWNERS files, the last matching pattern takes precedence.
+/// Patterns are matched against paths relative to the workspace root: patterns starting with `/` are anchored to it,
+/// patterns without a `/` match files in any directory, and patterns ending with `/` match everything under a
+/// directory. Alerts without a known location, or not matching any pattern, are delivered to the default owner.
+///
+/// Only file patterns are supported, not module paths such as `myapp::billing::*`, since the source location of
+/// panics and reported errors carries the file but not the module it belongs to.
+///
+/// Acknowledging and resolving alerts are delivered to the owner of the incident triggered with the same dedup key.
+/// Owners are remembered for the most recent incidents only, and not across restarts, so acknowledging and resolving
+/// alerts of unknown incidents are delivered to every owner.
+///
+/// ```
+/// use airbag::backends::{CodeOwners, PagerDuty};
+///
+/// let backend = CodeOwners::new()
+///     .rules(
+///         "
+///         ## billing and payments are owned by the billing team
+///         src/billing/**      billing
+///         src/payments/*.rs   billing
+///         ",
+///     )
+///     .unwrap()
+///     .owner("billing", PagerDuty::builder().token("billing routing key").build())
+///     .owner("platform", PagerDuty::builder().token("platform routing key").build())
+///     .default_owner("platform");
+/// ```
+#[derive(Default)]
+pub struct CodeOwners {
+    rules: Vec<(GlobMatcher, String)>,
+    owners: HashMap<String, Box<dyn Backend + Send>>,
+    default_owner: Option<String>,
+    /// Owners of triggered incidents by their dedup key, along with when they were triggered, so that resolving
+    /// alerts reach the same owner
+    incidents: HashMap<String, (String, Instant)>,
+}
+
+impl CodeOwners {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rule assigning the files matching the given pattern to the given owner
+    pub fn rule(mut self, pattern: &str, owner: impl Into<String>) -> anyhow::Result<Self> {
+        self.rules.push((compile_pattern(pattern)?, owner.into()));
+        Ok(self)
+    }
+
+    /// Adds the rules of a CODEOWNERS-style table, holding a pattern and an owner on each line. Empty lines and
+    /// lines starting with `#` are ignored
+    pub fn rules(mut self, table: &str) -> anyhow::Result<Self> {
+        for (index, line) in table.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let (Some(pattern), Some(owner), None) = (parts.next(), parts.next(), parts.next())
+            else {
+                anyhow::bail!(
+                    "Line {}: expected a pattern and an owner, got {line:?}",
+                    index + 1
+                );
+            };
+            self = self
+                .rule(pattern, owner)
+                .with_context(|| format!("Line {}", index + 1))?;
+        }
+        Ok(self)
+    }
+
+    /// Sets the backend delivering the alerts of the given owner
+    pub fn owner<B: Backend + Send + 'static>(
+        mut self,
+        owner: impl Into<String>,
+        backend: B,
+    ) -> Self {
+        self.owners.insert(owner.into(), Box::new(backend));
+        self
+    }
+
+    /// Sets the owner of alerts not matching any rule
+    pub fn default_owner(mut self, owner: impl Into<String>) -> Self {
+        self.default_owner = Some(owner.into());
+        self
+    }
+
+    fn owner_of(&self, alert: &Alert) -> Option<String> {
+        alert
+            .location()
+            .and_then(|location| {
+                self.rules
+                    .iter()
+                    .rev()
+                    .find(|(matcher, _)| matcher.is_match(&location.file))
+            })
+            .map(|(_, owner)| owner.clone())
+            .or_else(|| self.default_owner.clone())
+    }
+
+    fn deliver(&mut self, owner: &str, alert: Alert) -> anyhow::Result<()> {
+        log::debug!("Delivering alert #{} to owner {owner:?}", alert.id());
+        self.owners
+            .get_mut(owner)
+            .with_context(|| format!("No backend configured for owner {owner:?}"))
+            .map_err(super::permanent)?
+            .send(alert)
+    }
+
+    fn deliver_to_all(&mut self, alert: Alert) -> anyhow::Result<()> {
+        log::debug!(
+            "Owner of alert #{} is unknown, delivering to all owners",
+            alert.id()
+        );
+        let mut errors = Vec::new();
+        for (owner, backend) in &mut self.owners {
+            if let Err(e) = backend.send(alert.clone()) {
+                errors.push(format!("{owner}: {e:#}"));
+            }
+        }
+        if !self.owners.is_empty() && errors.len() == self.owners.len() {
+            anyhow::bail!(
+                "Failed sending alert to all {} owners: {}",
+                errors.len(),
+                errors.join("; ")
+            );
+        }
+        if !errors.is_empty() {
+            log::warn!(
+                "Alert #{} not delivered to every owner: {}",
+                alert.id(),
+                errors.join("; ")
+            );
+        }
+        Ok(())
+    }
+
+    fn remember_incident(&mut self, dedup_key: String, owner: String) {
+        if self.incidents.len() >= MAX_INCIDENTS && !self.incidents.contains_key(&dedup_key) {
+            let oldest = self
+                .incidents
+                .iter()
+                .min_by_key(|(_, (_, triggered_at))| *triggered_at)
+                .map(|(dedup_key, _)| dedup_key.clone());
+            if let Some(oldest) = oldest {
+                self.incidents.remove(&oldest);
+            }
+        }
+        self.incidents.insert(dedup_key, (owner, Instant::now()));
+    }
+}
+
+impl Backend for CodeOwners {
+    fn send(&mut self, alert: Alert) -> anyhow::Result<()> {
+        let dedup_key = alert.dedup_key().clone();
+
+        if alert.action() != Action::Trigger {
+            let owner = dedup_key
+                .as_ref()
+                .and_then(|dedup_key| self.incidents.get(dedup_key))
+                .map(|(owner, _)| owner.clone());
+            let action = alert.action();
+            match owner {
+                Some(owner) => self.deliver(&owner, alert)?,
+                None => return self.deliver_to_all(alert),
+            }
+            if let (Some(dedup_key), Action::Resolve) = (dedup_key, action) {
+                self.incidents.remove(&dedup_key);
+            }
+            return Ok(());
+        }
+
+        let owner = self
+            .owner_of(&alert)
+            .context("Alert does not match any rule, and no default owner is set")
+            .map_err(super::permanent)?;
+        self.deliver(&owner, alert)?;
+        if let Some(dedup_key) = dedup_key {
+            self.remember_incident(dedup_key, owner);
+        }
+        Ok(())
+    }
+
+    fn on_start(&mut self) {
+        for backend in self.owners.values_mut() {
+            backend.on_start();
+        }
+    }
+}
+
+/// Compiles a CODEOWNERS-style pattern into a glob matching paths relative to the workspace root
+pub(crate) fn compile_pattern(pattern: &str) -> anyhow::Result<GlobMatcher> {
+    let mut glob = match pattern.strip_prefix('/') {
+        Some(anchored) => anchored.to_owned(),
+        None if !pattern.trim_end_matches('/').contains('/') => format!("**/{pattern}"),
+        None => pattern.to_owned(),
+    };
+    if glob.ends_with('/') {
+        glob.push_str("**");
+    }
+    Ok(GlobBuilder::new(&glob)
+        .literal_separator(true)
+        .build()
+        .with_context(|| format!("Invalid pattern {pattern:?}"))?
+        .compile_matcher())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::compile_pattern;
+
+    #[test]
+    fn test_compile_pattern() {
+        let matches = |pattern: &str, path: &str| compile_pattern(pattern).unwrap().is_match(path);
+
+        assert!(matches("src/billing/**", "src/billing/invoices/mod.rs"));
+        assert!(!matches("src/billing/**", "src/payments/mod.rs"));
+        assert!(matches("src/billing/", "src/billing/mod.rs"));
+        assert!(matches("src/*.rs", "src/main.rs"));
+        assert!(!matches("src/*.rs", "src/billing/mod.rs"));
+        assert!(matches("billing.rs", "src/billing.rs"));
+        assert!(matches("billing.rs", "billing.rs"));
+        assert!(matches("/src/main.rs", "src/main.rs"));
+        assert!(!matches("/main.rs", "src/main.rs"));
+        assert!(compile_pattern("src/[billing").is_err());
+    }
+}
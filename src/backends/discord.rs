@@ -0,0 +1,144 @@
+use anyhow::Context;
+use reqwest::Url;
+use serde_json::json;
+
+use super::{slack, RetryPolicy};
+use crate::{
+    alert::{Action, AlertMeta},
+    http::HttpRequest,
+    utils::{field_text, truncate},
+};
+
+const MAX_TITLE_LENGTH: usize = 256;
+const MAX_DESCRIPTION_LENGTH: usize = 4096;
+const MAX_FIELD_NAME_LENGTH: usize = 256;
+const MAX_FIELD_VALUE_LENGTH: usize = 1024;
+const MAX_FOOTER_LENGTH: usize = 2048;
+const MAX_FIELDS: usize = 25;
+const MAX_EMBED_LENGTH: usize = 6000;
+
+/// The `Discord` struct implements a backend posting alerts to a Discord channel through a
+/// [webhook](https://support.discord.com/hc/en-us/articles/228383668).
+///
+/// Alerts are rendered as embeds colored by the alert's severity, showing the title, the description, the alert's
+/// fields and its dedup key. Texts exceeding Discord's embed limits are truncated, and only the first 25 fields are
+/// shown. Since Discord also limits the total length of an embed to 6000 characters, the description and fields are
+/// shortened, and trailing fields dropped, as needed to fit it.
+///
+/// Discord has no notion of incidents, so acknowledging and resolving alerts post a follow-up message referring to
+/// the original alert's dedup key.
+///
+/// ```
+/// use airbag::backends::Discord;
+///
+/// let backend = Discord::builder()
+///     .webhook_url("https://discord.com/api/webhooks/1234/some-token")
+///     .build();
+/// ```
+#[derive(typed_builder::TypedBuilder)]
+pub struct Discord {
+    #[builder(setter(into))]
+    webhook_url: String,
+
+    /// Overrides the webhook's default username
+    #[builder(default, setter(strip_option, into))]
+    username: Option<String>,
+
+    #[builder(default)]
+    retry_policy: RetryPolicy,
+}
+
+impl_http_backend!(Discord);
+
+impl Discord {
+    fn request(&self, alert: &crate::alert::Alert) -> anyhow::Result<HttpRequest> {
+        let url = Url::parse(&self.webhook_url).context("Cannot parse URL")?;
+
+        let AlertMeta {
+            title,
+            description,
+            dedup_key,
+            action,
+            ..
+        } = alert.meta();
+
+        let severity = alert.meta().effective_severity();
+        let title = title.as_deref().unwrap_or("Airbag alert");
+
+        let (title, color) = match action {
+            Action::Trigger => (title.to_owned(), slack::color(severity)),
+            Action::Acknowledge => (format!("Acknowledged: {title}"), slack::ACKNOWLEDGED_COLOR),
+            Action::Resolve => (format!("Resolved: {title}"), slack::RESOLVED_COLOR),
+        };
+
+        let title = truncate(&title, MAX_TITLE_LENGTH);
+        let footer = dedup_key
+            .as_ref()
+            .map(|dedup_key| truncate(&format!("Dedup key: {dedup_key}"), MAX_FOOTER_LENGTH));
+
+        // the footer is accounted for up front, so that the dedup key is never dropped in favor of fields
+        let mut budget = MAX_EMBED_LENGTH
+            - title.chars().count()
+            - footer.as_ref().map_or(0, |footer| footer.chars().count());
+
+        let mut embed = json!({
+            "title": title,
+            "color": u32::from_str_radix(color.trim_start_matches('#'), 16).unwrap(),
+        });
+
+        if let Some(description) = description {
+            let description = truncate(description, MAX_DESCRIPTION_LENGTH.min(budget));
+            budget -= description.chars().count();
+            embed["description"] = json!(description);
+        }
+
+        let mut fields = Vec::new();
+        for (name, value) in alert
+            .get_fields()
+            .as_object()
+            .into_iter()
+            .flatten()
+            .take(MAX_FIELDS)
+        {
+            let name = truncate(or_placeholder(name), MAX_FIELD_NAME_LENGTH);
+            let value = field_text(value);
+            let value = truncate(
+                or_placeholder(&value),
+                MAX_FIELD_VALUE_LENGTH.min(budget.saturating_sub(name.chars().count())),
+            );
+
+            let length = name.chars().count() + value.chars().count();
+            if length > budget {
+                break;
+            }
+            budget -= length;
+            fields.push(json!({"name": name, "value": value, "inline": true}));
+        }
+        if !fields.is_empty() {
+            embed["fields"] = json!(fields);
+        }
+
+        if let Some(footer) = footer {
+            embed["footer"] = json!({ "text": footer });
+        }
+
+        let mut json = json!({"embeds": [embed]});
+        if let Some(username) = &self.username {
+            json["username"] = json!(username);
+        }
+
+        log::debug!("Discord message: {json:?}");
+
+        log::debug!("Sending alert #{} to Discord", alert.id());
+        Ok(HttpRequest::post_json(url, json))
+    }
+}
+
+/// Discord rejects embed fields with an empty name or value, so these are replaced with a zero-width space
+fn or_placeholder(s: &str) -> &str {
+    if s.trim().is_empty() {
+        "\u{200b}"
+    } else {
+        s
+    }
+}
@@ -0,0 +1,220 @@
+use anyhow::Context;
+use lettre::{
+    message::{Mailbox, MultiPart},
+    transport::smtp::{authentication::Credentials, SmtpTransport},
+    Message, Transport,
+};
+
+use super::RetryPolicy;
+use crate::{
+    alert::{Action, AlertMeta},
+    utils::field_text,
+};
+
+/// The transport security used to connect to an SMTP server
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SmtpSecurity {
+    /// Plain-text connection, typically on port 25. Only suitable for local relays
+    None,
+    /// Upgrades a plain-text connection using `STARTTLS`, typically on port 587
+    #[default]
+    StartTls,
+    /// Implicit TLS, typically on port 465
+    Tls,
+}
+
+/// The `Email` struct implements a backend sending alerts by email over SMTP. Available with the `email` feature.
+///
+/// The subject is built from the alert's title and severity, and the body, sent as both plain text and HTML, holds
+/// the alert's description, dedup key and fields. Acknowledging and resolving alerts send a follow-up email referring
+/// to the original alert's dedup key.
+///
+/// Failed deliveries are retried according to the backend's [RetryPolicy], unless the server rejects the message
+/// permanently.
+///
+/// ```
+/// use airbag::backends::{email::SmtpSecurity, Email};
+///
+/// let backend = Email::builder()
+///     .host("smtp.example.com")
+///     .security(SmtpSecurity::Tls)
+///     .username("alerts@example.com")
+///     .password("some-password")
+///     .from("Airbag <alerts@example.com>")
+///     .to(vec!["oncall@example.com".to_owned()])
+///     .build();
+/// ```
+#[derive(typed_builder::TypedBuilder)]
+pub struct Email {
+    #[builder(setter(into))]
+    host: String,
+
+    /// Overrides the default port of the chosen [SmtpSecurity]
+    #[builder(default, setter(strip_option))]
+    port: Option<u16>,
+
+    #[builder(default)]
+    security: SmtpSecurity,
+
+    #[builder(default, setter(strip_option, into))]
+    username: Option<String>,
+
+    #[builder(default, setter(strip_option, into))]
+    password: Option<String>,
+
+    #[builder(setter(into))]
+    from: String,
+
+    to: Vec<String>,
+
+    #[builder(default)]
+    retry_policy: RetryPolicy,
+}
+
+impl super::Backend for Email {
+    fn send(&mut self, alert: crate::Alert) -> anyhow::Result<()> {
+        let message = self.message(&alert)?;
+        log::debug!("Sending alert #{} by email", alert.id());
+        send_with_retries(&self.transport()?, &message, &self.retry_policy)
+    }
+}
+
+#[cfg(feature = "tokio")]
+impl super::AsyncBackend for Email {
+    async fn send(&self, alert: crate::Alert) -> anyhow::Result<()> {
+        let message = self.message(&alert)?;
+        let transport = self.transport()?;
+        let retry_policy = self.retry_policy.clone();
+        log::debug!("Sending alert #{} by email", alert.id());
+        tokio::task::spawn_blocking(move || send_with_retries(&transport, &message, &retry_policy))
+            .await
+            .context("Email delivery task failed")?
+    }
+}
+
+impl Email {
+    fn transport(&self) -> anyhow::Result<SmtpTransport> {
+        let mut builder = match self.security {
+            SmtpSecurity::None => SmtpTransport::builder_dangerous(&self.host),
+            SmtpSecurity::StartTls => SmtpTransport::starttls_relay(&self.host)
+                .context("Cannot configure SMTP transport")?,
+            SmtpSecurity::Tls => {
+                SmtpTransport::relay(&self.host).context("Cannot configure SMTP transport")?
+            }
+        };
+        if let Some(port) = self.port {
+            builder = builder.port(port);
+        }
+        if let Some(username) = &self.username {
+            builder = builder.credentials(Credentials::new(
+                username.clone(),
+                self.password.clone().unwrap_or_default(),
+            ));
+        }
+        Ok(builder.build())
+    }
+
+    fn message(&self, alert: &crate::alert::Alert) -> anyhow::Result<Message> {
+        let AlertMeta {
+            title,
+            description,
+            dedup_key,
+            action,
+            ..
+        } = alert.meta();
+
+        let severity = alert.meta().effective_severity();
+        let title = title
+            .as_deref()
+            .or(dedup_key.as_deref())
+            .unwrap_or("Airbag alert");
+        let subject = match action {
+            Action::Trigger => format!("[{}] {title}", severity.as_str().to_uppercase()),
+            Action::Acknowledge => format!("[ACKNOWLEDGED] {title}"),
+            Action::Resolve => format!("[RESOLVED] {title}"),
+        };
+
+        let mut details = vec![("Severity".to_owned(), severity.as_str().to_owned())];
+        if let Some(dedup_key) = dedup_key {
+            details.push(("Dedup key".to_owned(), dedup_key.clone()));
+        }
+        details.extend(
+            alert
+                .get_fields()
+                .as_object()
+                .into_iter()
+                .flatten()
+                .map(|(name, value)| (name.clone(), field_text(value))),
+        );
+
+        let mut text = format!("{title}\n\n");
+        let mut html = format!("<h2>{}</h2>\n", escape_html(title));
+        if let Some(description) = description {
+            text.push_str(&format!("{description}\n\n"));
+            html.push_str(&format!("<pre>{}</pre>\n", escape_html(description)));
+        }
+        html.push_str("<table>\n");
+        for (name, value) in &details {
+            text.push_str(&format!("{name}: {value}\n"));
+            html.push_str(&format!(
+                "<tr><th align=\"left\">{}</th><td>{}</td></tr>\n",
+                escape_html(name),
+                escape_html(value)
+            ));
+        }
+        html.push_str("</table>\n");
+
+        let mut builder = Message::builder()
+            .from(
+                self.from
+                    .parse::<Mailbox>()
+                    .context("Invalid sender address")?,
+            )
+            .subject(subject);
+        for to in &self.to {
+            builder = builder.to(to
+                .parse::<Mailbox>()
+                .with_context(|| format!("Invalid recipient address: {to:?}"))?);
+        }
+        builder
+            .multipart(MultiPart::alternative_plain_html(text, html))
+            .context("Cannot build email message")
+    }
+}
+
+fn send_with_retries(
+    transport: &SmtpTransport,
+    message: &Message,
+    policy: &RetryPolicy,
+) -> anyhow::Result<()> {
+    let start = std::time::Instant::now();
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        let e = match transport.send(message) {
+            Ok(_) => return Ok(()),
+            Err(e) if e.is_permanent() => {
+                return Err(super::permanent(
+                    anyhow::Error::new(e).context("SMTP server rejected the message"),
+                ))
+            }
+            Err(e) => anyhow::Error::new(e).context("Failed sending email"),
+        };
+        match policy.next_delay(attempts, start.elapsed(), None) {
+            Some(delay) => {
+                log::error!("Error while sending email: {e:?}. Retrying in {delay:?}...");
+                std::thread::sleep(delay);
+            }
+            None => {
+                return Err(e.context(format!("Giving up sending email after {attempts} attempts")))
+            }
+        }
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
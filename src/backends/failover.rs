@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+use crossbeam::channel::{Receiver, RecvTimeoutError, Sender, TryRecvError};
+
+use super::Backend;
+use crate::{alert::Action, Alert};
+
+type Reply = anyhow::Result<()>;
+
+enum WorkerMessage {
+    Start,
+    Send(Alert, Sender<Reply>),
+}
+
+struct Child {
+    name: &'static str,
+    sender: Sender<WorkerMessage>,
+    /// The reply of an attempt which exceeded the timeout, and may still be in progress
+    pending: Option<Receiver<Reply>>,
+}
+
+/// The `Failover` struct implements a backend trying a chain of backends in order, until one of them delivers the
+/// alert. For example, paging through PagerDuty, falling back to SquadCast if PagerDuty fails.
+///
+/// A backend is skipped if sending fails, or if it does not complete within the configured timeout, in which case
+/// its attempt continues in the background. Until that attempt completes, the backend is skipped for subsequent
+/// alerts. Sending fails only if all backends were skipped, with an error describing each failure.
+///
+/// Acknowledging and resolving alerts are sent to every backend rather than only the first available one, since the
+/// incident may have been opened by any of them. They succeed if at least one backend delivers them.
+///
+/// Since backends usually retry failed requests, a timeout is required for falling back during outages, unless their
+/// [RetryPolicy](super::RetryPolicy) is bounded:
+///
+/// ```
+/// use std::time::Duration;
+/// use airbag::backends::{Failover, PagerDuty, SquadCast};
+///
+/// let backend = Failover::new()
+///     .with_backend(PagerDuty::builder().token("your token").build())
+///     .with_backend(SquadCast::builder().token("your token").region("eu").build())
+///     .timeout(Duration::from_secs(30));
+/// ```
+#[derive(Default)]
+pub struct Failover {
+    children: Vec<Child>,
+    timeout: Option<Duration>,
+}
+
+impl Failover {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a backend to the end of the chain
+    pub fn with_backend<B: Backend + Send + 'static>(mut self, mut backend: B) -> Self {
+        let name = std::any::type_name::<B>();
+        let (sender, receiver) = crossbeam::channel::unbounded();
+        std::thread::Builder::new()
+            .name("airbag-failover".into())
+            .spawn(move || {
+                for msg in receiver {
+                    match msg {
+                        WorkerMessage::Start => backend.on_start(),
+                        WorkerMessage::Send(alert, reply) => {
+                            let _ = reply.send(backend.send(alert));
+                        }
+                    }
+                }
+                log::debug!("Failover worker for {name} terminating");
+            })
+            .expect("Cannot spawn failover worker thread");
+
+        self.children.push(Child {
+            name,
+            sender,
+            pending: None,
+        });
+        self
+    }
+
+    /// Sets the time to wait for each backend before falling back to the next one
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+}
+
+impl Child {
+    fn send(&mut self, alert: Alert, timeout: Option<Duration>) -> anyhow::Result<()> {
+        if let Some(pending) = &self.pending {
+            match pending.try_recv() {
+                Err(TryRecvError::Empty) => anyhow::bail!("still sending a previous alert"),
+                Ok(_) | Err(TryRecvError::Disconnected) => self.pending = None,
+            }
+        }
+
+        let (reply_sender, reply) = crossbeam::channel::bounded(1);
+        self.sender
+            .send(WorkerMessage::Send(alert, reply_sender))
+            .map_err(|_| anyhow::format_err!("backend worker terminated"))?;
+
+        let res = match timeout {
+            Some(timeout) => reply.recv_timeout(timeout),
+            None => reply.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        };
+        match res {
+            Ok(res) => res,
+            Err(RecvTimeoutError::Timeout) => {
+                self.pending = Some(reply);
+                anyhow::bail!("timed out after {:?}", timeout.unwrap_or_default())
+            }
+            Err(RecvTimeoutError::Disconnected) => anyhow::bail!("backend worker terminated"),
+        }
+    }
+}
+
+impl Backend for Failover {
+    fn send(&mut self, alert: Alert) -> anyhow::Result<()> {
+        let broadcast = alert.action() != Action::Trigger;
+        let mut delivered = false;
+        let mut errors = Vec::new();
+        for child in &mut self.children {
+            match child.send(alert.clone(), self.timeout) {
+                Ok(()) if broadcast => delivered = true,
+                Ok(()) => {
+                    if !errors.is_empty() {
+                        log::warn!(
+                            "Alert #{} delivered by {} after failing over: {}",
+                            alert.id(),
+                            child.name,
+                            errors.join("; ")
+                        );
+                    }
+                    return Ok(());
+                }
+                Err(e) => {
+                    log::error!(
+                        "Failed sending alert #{} to {}: {e:?}",
+                        alert.id(),
+                        child.name
+                    );
+                    errors.push(format!("{}: {e:#}", child.name));
+                }
+            }
+        }
+        if delivered {
+            if !errors.is_empty() {
+                log::warn!(
+                    "Alert #{} not delivered to every backend: {}",
+                    alert.id(),
+                    errors.join("; ")
+                );
+            }
+            return Ok(());
+        }
+        anyhow::bail!(
+            "Failed sending alert to all {} backends: {}",
+            self.children.len(),
+            errors.join("; ")
+        )
+    }
+
+    fn on_start(&mut self) {
+        for child in &self.children {
+            let _ = child.sender.send(WorkerMessage::Start);
+        }
+    }
+}
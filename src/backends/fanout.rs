@@ -0,0 +1,102 @@
+use super::Backend;
+use crate::Alert;
+
+struct Child {
+    name: &'static str,
+    backend: Box<dyn Backend + Send>,
+}
+
+/// The `FanOut` struct implements a backend delivering each alert to several other backends, e.g. paging through
+/// PagerDuty while also posting to Slack.
+///
+/// Each alert is sent to every child backend, even when some of them fail. Sending fails if any of the children
+/// failed, with an error describing each failure. By default, children are sent to one after the other. In parallel
+/// mode, each child is sent to from its own thread, so that a slow backend does not delay the others
+///
+/// ```
+/// use airbag::backends::{FanOut, PagerDuty, Slack};
+///
+/// let backend = FanOut::new()
+///     .with_backend(PagerDuty::builder().token("your token").build())
+///     .with_backend(Slack::builder().webhook_url("https://hooks.slack.com/services/T000/B000/XXXX").build())
+///     .parallel(true);
+/// ```
+#[derive(Default)]
+pub struct FanOut {
+    children: Vec<Child>,
+    parallel: bool,
+}
+
+impl FanOut {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a child backend, to which every alert will be sent
+    pub fn with_backend<B: Backend + Send + 'static>(mut self, backend: B) -> Self {
+        self.children.push(Child {
+            name: std::any::type_name::<B>(),
+            backend: Box::new(backend),
+        });
+        self
+    }
+
+    /// Controls whether alerts are sent to all children concurrently
+    pub fn parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+}
+
+impl Backend for FanOut {
+    fn send(&mut self, alert: Alert) -> anyhow::Result<()> {
+        let total = self.children.len();
+        let results = if self.parallel && total > 1 {
+            std::thread::scope(|scope| {
+                let handles = self
+                    .children
+                    .iter_mut()
+                    .map(|child| {
+                        let alert = alert.clone();
+                        scope.spawn(move || child.backend.send(alert))
+                    })
+                    .collect::<Vec<_>>();
+                handles
+                    .into_iter()
+                    .map(|handle| {
+                        handle
+                            .join()
+                            .unwrap_or_else(|_| Err(anyhow::format_err!("Backend panicked")))
+                    })
+                    .collect::<Vec<_>>()
+            })
+        } else {
+            self.children
+                .iter_mut()
+                .map(|child| child.backend.send(alert.clone()))
+                .collect()
+        };
+
+        let errors = self
+            .children
+            .iter()
+            .zip(results)
+            .filter_map(|(child, res)| res.err().map(|e| format!("{}: {e:#}", child.name)))
+            .collect::<Vec<_>>();
+
+        if errors.is_empty() {
+            return Ok(());
+        }
+        anyhow::bail!(
+            "Failed sending alert to {} of {total} backends: {}",
+            errors.len(),
+            errors.join("; ")
+        )
+    }
+
+    fn on_start(&mut self) {
+        for child in &mut self.children {
+            child.backend.on_start();
+        }
+    }
+}
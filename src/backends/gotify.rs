@@ -0,0 +1,94 @@
+use anyhow::Context;
+use reqwest::{Method, Url};
+use serde_json::json;
+
+use super::RetryPolicy;
+use crate::{
+    alert::{Action, AlertMeta, Priority},
+    http::HttpRequest,
+    utils::plain_text_message,
+};
+
+/// The `Gotify` struct implements a backend sending alerts as push notifications through a
+/// [Gotify](https://gotify.net) server, using an application token.
+///
+/// Alert priorities map to Gotify's numeric priorities, from 10 for [Priority::P1] down to 1 for [Priority::P5]. The
+/// notification's message holds the alert's description and fields.
+///
+/// Gotify has no notion of incidents, so acknowledging and resolving alerts send a follow-up notification referring
+/// to the original alert's dedup key.
+///
+/// ```
+/// use airbag::backends::Gotify;
+///
+/// let backend = Gotify::builder()
+///     .base_url("https://gotify.example.com")
+///     .app_token("some-app-token")
+///     .build();
+/// ```
+#[derive(typed_builder::TypedBuilder)]
+pub struct Gotify {
+    #[builder(setter(into))]
+    base_url: String,
+
+    #[builder(setter(into))]
+    app_token: String,
+
+    #[builder(default)]
+    retry_policy: RetryPolicy,
+}
+
+impl_http_backend!(Gotify);
+
+impl Gotify {
+    fn request(&self, alert: &crate::alert::Alert) -> anyhow::Result<HttpRequest> {
+        let mut url = Url::parse(&self.base_url).context("Cannot parse URL")?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow::format_err!("Cannot use base URL"))?
+            .pop_if_empty()
+            .push("message");
+
+        let AlertMeta {
+            title,
+            dedup_key,
+            action,
+            ..
+        } = alert.meta();
+
+        let priority = alert.meta().effective_priority();
+        let title = title
+            .as_deref()
+            .or(dedup_key.as_deref())
+            .unwrap_or("Airbag alert");
+
+        let (title, priority) = match action {
+            Action::Trigger => (
+                title.to_owned(),
+                match priority {
+                    Priority::P1 => 10,
+                    Priority::P2 => 8,
+                    Priority::P3 => 5,
+                    Priority::P4 => 3,
+                    Priority::P5 => 1,
+                },
+            ),
+            Action::Acknowledge => (format!("Acknowledged: {title}"), 5),
+            Action::Resolve => (format!("Resolved: {title}"), 5),
+        };
+
+        let message = plain_text_message(alert, dedup_key.as_deref());
+
+        let json = json!({
+            "title": title,
+            "message": if message.is_empty() { &title } else { &message },
+            "priority": priority,
+        });
+
+        log::debug!("Gotify message: {json:?}");
+
+        log::debug!("Sending alert #{} to Gotify", alert.id());
+        Ok(HttpRequest::new(Method::POST, url)
+            .header("X-Gotify-Key", &self.app_token)
+            .json(json))
+    }
+}
@@ -0,0 +1,124 @@
+use anyhow::Context;
+use reqwest::Url;
+use serde_json::json;
+
+use super::{slack, RetryPolicy};
+use crate::{
+    alert::{Action, AlertMeta},
+    http::HttpRequest,
+    utils::{field_text, truncate},
+};
+
+// Mattermost limits posts, including their attachments, to 16383 characters. Together, these limits keep every text
+// of an alert within it, the title counting twice as it is also the fallback
+const MAX_TITLE_LENGTH: usize = 1000;
+const MAX_DESCRIPTION_LENGTH: usize = 8000;
+const MAX_FIELD_TITLE_LENGTH: usize = 100;
+const MAX_FIELD_VALUE_LENGTH: usize = 1000;
+const MAX_FIELDS: usize = 5;
+const MAX_FOOTER_LENGTH: usize = 300;
+
+/// The `Mattermost` struct implements a backend posting alerts to a Mattermost channel through an
+/// [incoming webhook](https://developers.mattermost.com/integrate/webhooks/incoming/).
+///
+/// Alerts are rendered as Slack-compatible message attachments colored by the alert's severity, showing the title,
+/// the description in a code block, the alert's fields and its dedup key. Texts exceeding Mattermost's post limits
+/// are truncated, and only the first 5 fields are shown.
+///
+/// Mattermost has no notion of incidents, so acknowledging and resolving alerts post a follow-up message referring
+/// to the original alert's dedup key.
+///
+/// ```
+/// use airbag::backends::Mattermost;
+///
+/// let backend = Mattermost::builder()
+///     .webhook_url("https://mattermost.example.com/hooks/some-id")
+///     .channel("alerts")
+///     .build();
+/// ```
+#[derive(typed_builder::TypedBuilder)]
+pub struct Mattermost {
+    #[builder(setter(into))]
+    webhook_url: String,
+
+    /// Overrides the webhook's default channel
+    #[builder(default, setter(strip_option, into))]
+    channel: Option<String>,
+
+    #[builder(default)]
+    retry_policy: RetryPolicy,
+}
+
+impl_http_backend!(Mattermost);
+
+impl Mattermost {
+    fn request(&self, alert: &crate::alert::Alert) -> anyhow::Result<HttpRequest> {
+        let url = Url::parse(&self.webhook_url).context("Cannot parse URL")?;
+
+        let AlertMeta {
+            title,
+            description,
+            dedup_key,
+            action,
+            ..
+        } = alert.meta();
+
+        let severity = alert.meta().effective_severity();
+        let title = title.as_deref().unwrap_or("Airbag alert");
+
+        let (title, color) = match action {
+            Action::Trigger => (title.to_owned(), slack::color(severity)),
+            Action::Acknowledge => (format!("Acknowledged: {title}"), slack::ACKNOWLEDGED_COLOR),
+            Action::Resolve => (format!("Resolved: {title}"), slack::RESOLVED_COLOR),
+        };
+        let title = truncate(&title, MAX_TITLE_LENGTH);
+
+        let mut attachment = json!({
+            "fallback": title,
+            "color": color,
+            "title": title,
+        });
+
+        if let Some(description) = description {
+            attachment["text"] = json!(format!(
+                "```\n{}\n```",
+                slack::escape_code_block(&truncate(description, MAX_DESCRIPTION_LENGTH))
+            ));
+        }
+
+        let fields = alert
+            .get_fields()
+            .as_object()
+            .into_iter()
+            .flatten()
+            .take(MAX_FIELDS)
+            .map(|(name, value)| {
+                json!({
+                    "title": truncate(name, MAX_FIELD_TITLE_LENGTH),
+                    "value": truncate(&field_text(value), MAX_FIELD_VALUE_LENGTH),
+                    "short": true,
+                })
+            })
+            .collect::<Vec<_>>();
+        if !fields.is_empty() {
+            attachment["fields"] = json!(fields);
+        }
+
+        if let Some(dedup_key) = dedup_key {
+            attachment["footer"] = json!(truncate(
+                &format!("Dedup key: {dedup_key}"),
+                MAX_FOOTER_LENGTH
+            ));
+        }
+
+        let mut json = json!({"attachments": [attachment]});
+        if let Some(channel) = &self.channel {
+            json["channel"] = json!(channel);
+        }
+
+        log::debug!("Mattermost message: {json:?}");
+
+        log::debug!("Sending alert #{} to Mattermost", alert.id());
+        Ok(HttpRequest::post_json(url, json))
+    }
+}
@@ -1,11 +1,130 @@
 use crate::Alert;
 
+/// Implements [Backend] (and [AsyncBackend], when enabled) for HTTP-based backends, which build their requests using
+/// a `request` method and retry them according to their `retry_policy` field
+macro_rules! impl_http_backend {
+    ($backend:ty) => {
+        impl $crate::backends::Backend for $backend {
+            fn send(&mut self, alert: $crate::alert::Alert) -> anyhow::Result<()> {
+                self.request(&alert)?.send(&self.retry_policy)
+            }
+        }
+
+        #[cfg(feature = "tokio")]
+        impl $crate::backends::AsyncBackend for $backend {
+            async fn send(&self, alert: $crate::alert::Alert) -> anyhow::Result<()> {
+                self.request(&alert)?.send_async(&self.retry_policy).await
+            }
+        }
+    };
+}
+
+/// Returns early with an error no retry can recover from, see [PermanentError]
+macro_rules! bail_permanent {
+    ($($arg:tt)*) => {
+        return Err($crate::backends::permanent(anyhow::format_err!($($arg)*)))
+    };
+}
+
+pub mod alertmanager;
+pub mod codeowners;
+pub mod discord;
+#[cfg(feature = "email")]
+pub mod email;
+pub mod failover;
+pub mod fanout;
+pub mod gotify;
+pub mod mattermost;
+pub mod ntfy;
+pub mod opsgenie;
 pub mod pagerduty;
+pub mod pushover;
+pub mod retry;
+pub mod router;
+pub mod sentry;
+pub mod slack;
+pub mod spool;
 pub mod squadcast;
+pub mod teams;
+pub mod victorops;
+pub mod webhook;
 
+pub use alertmanager::Alertmanager;
+pub use codeowners::CodeOwners;
+pub use discord::Discord;
+#[cfg(feature = "email")]
+pub use email::Email;
+pub use failover::Failover;
+pub use fanout::FanOut;
+pub use gotify::Gotify;
+pub use mattermost::Mattermost;
+pub use ntfy::Ntfy;
+pub use opsgenie::Opsgenie;
 pub use pagerduty::PagerDuty;
+pub use pushover::Pushover;
+pub use retry::RetryPolicy;
+pub use router::Router;
+pub use sentry::Sentry;
+pub use slack::Slack;
+pub use spool::Spool;
 pub use squadcast::SquadCast;
+pub use teams::Teams;
+pub use victorops::VictorOps;
+pub use webhook::Webhook;
+
+/// An error no retry can recover from, e.g. a request rejected by the service or an action a backend does not
+/// support. Spooled alerts failing with such errors are discarded rather than kept for replaying
+#[derive(Debug)]
+pub(crate) struct PermanentError(anyhow::Error);
+
+impl std::fmt::Display for PermanentError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl std::error::Error for PermanentError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+/// Marks an error as permanent
+pub(crate) fn permanent(e: anyhow::Error) -> anyhow::Error {
+    anyhow::Error::new(PermanentError(e))
+}
+
+/// Returns whether an error, or any of its causes, is permanent
+pub(crate) fn is_permanent(e: &anyhow::Error) -> bool {
+    e.chain().any(|e| e.is::<PermanentError>())
+}
 
+/// A backend is responsible for delivering alerts to a 3rd party service.
+///
+/// Alerts carry a lifecycle [Action](crate::alert::Action) which backends are expected to honor: triggering a new
+/// incident, or acknowledging/resolving the incident previously triggered with the same dedup key. Backends that
+/// cannot express a given action should return an error from [Backend::send] rather than silently triggering a new
+/// incident
 pub trait Backend {
     fn send(&mut self, alert: Alert) -> anyhow::Result<()>;
+
+    /// Called once from the hub's delivery thread when the hub is configured, before any alert is sent. Backends
+    /// wrapping other backends should forward it to them
+    fn on_start(&mut self) {}
+
+    /// Called once when the hub is configured, opening the spool through which the hub persists alerts until they
+    /// are delivered. Opening a spool creates its directory and locks it for as long as the hub runs. Implemented by
+    /// [Spool]
+    fn open_spool(&self) -> Option<spool::SpoolHandle> {
+        None
+    }
+}
+
+/// The async counterpart of [Backend], used by hubs configured with [configure_async](crate::configure_async).
+///
+/// Unlike [Backend::send], [AsyncBackend::send] takes `&self`, as multiple alerts may be delivered concurrently. All
+/// of Airbag's built-in HTTP backends implement both traits
+#[cfg(feature = "tokio")]
+pub trait AsyncBackend: Send + Sync + 'static {
+    fn send(&self, alert: Alert) -> impl std::future::Future<Output = anyhow::Result<()>> + Send;
 }
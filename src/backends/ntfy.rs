@@ -0,0 +1,89 @@
+use super::RetryPolicy;
+use crate::{
+    alert::{Action, AlertMeta, Priority},
+    http::HttpRequest,
+    utils::plain_text_message,
+};
+use anyhow::Context;
+use reqwest::{Method, Url};
+
+/// The `Ntfy` struct implements a backend publishing alerts as push notifications to an [ntfy](https://ntfy.sh) topic,
+/// either on ntfy.sh or on a self-hosted server.
+///
+/// Alert priorities map to ntfy's priorities, from `urgent` for [Priority::P1] down to `min` for [Priority::P5]. The
+/// notification's message holds the alert's description and fields.
+///
+/// ntfy has no notion of incidents, so acknowledging and resolving alerts publish a follow-up notification referring
+/// to the original alert's dedup key.
+///
+/// ```
+/// use airbag::backends::Ntfy;
+///
+/// let backend = Ntfy::builder()
+///     .topic_url("https://ntfy.example.com/alerts")
+///     .access_token("tk_some-token")
+///     .build();
+/// ```
+#[derive(typed_builder::TypedBuilder)]
+pub struct Ntfy {
+    #[builder(setter(into))]
+    topic_url: String,
+
+    #[builder(default, setter(strip_option, into))]
+    access_token: Option<String>,
+
+    #[builder(default)]
+    retry_policy: RetryPolicy,
+}
+
+impl_http_backend!(Ntfy);
+
+impl Ntfy {
+    fn request(&self, alert: &crate::alert::Alert) -> anyhow::Result<HttpRequest> {
+        let mut url = Url::parse(&self.topic_url).context("Cannot parse URL")?;
+
+        let AlertMeta {
+            title,
+            dedup_key,
+            action,
+            ..
+        } = alert.meta();
+
+        let priority = alert.meta().effective_priority();
+        let title = title
+            .as_deref()
+            .or(dedup_key.as_deref())
+            .unwrap_or("Airbag alert");
+
+        let (title, priority, tag) = match action {
+            Action::Trigger => (
+                title.to_owned(),
+                match priority {
+                    Priority::P1 => "5",
+                    Priority::P2 => "4",
+                    Priority::P3 => "3",
+                    Priority::P4 => "2",
+                    Priority::P5 => "1",
+                },
+                "rotating_light",
+            ),
+            Action::Acknowledge => (format!("Acknowledged: {title}"), "3", "eyes"),
+            Action::Resolve => (format!("Resolved: {title}"), "3", "white_check_mark"),
+        };
+
+        let message = plain_text_message(alert, dedup_key.as_deref());
+
+        // the title is passed as a query parameter rather than a header, as headers cannot hold arbitrary unicode
+        url.query_pairs_mut().append_pair("title", &title);
+
+        let mut request = HttpRequest::new(Method::POST, url)
+            .header("Priority", priority)
+            .header("Tags", tag);
+        if let Some(access_token) = &self.access_token {
+            request = request.header("Authorization", format!("Bearer {access_token}"));
+        }
+
+        log::debug!("Sending alert #{} to ntfy", alert.id());
+        Ok(request.text(if message.is_empty() { title } else { message }))
+    }
+}
@@ -0,0 +1,146 @@
+use anyhow::Context;
+use reqwest::{Method, Url};
+use serde_json::json;
+
+use super::RetryPolicy;
+use crate::{
+    alert::{Action, AlertMeta, Priority},
+    http::HttpRequest,
+    utils::{field_text, truncate},
+};
+
+const MAX_MESSAGE_LENGTH: usize = 130;
+const MAX_DESCRIPTION_LENGTH: usize = 15000;
+
+/// The `Opsgenie` struct implements a backend for the [Opsgenie](https://www.atlassian.com/software/opsgenie) service,
+/// using its Alert API.
+///
+/// Alert titles are sent as the alert message, and dedup keys as the alert alias. Custom fields are sent as alert
+/// details, except for a `tags` field, which is expected to hold a list of tags.
+///
+/// All alert [actions](crate::alert::Action) are supported. Acknowledging and resolving refer to the alert triggered
+/// with the same dedup key, and map to acknowledging and closing it respectively.
+///
+/// The region defaults to "us". Accounts hosted in Europe should use the "eu" region:
+///
+/// ```
+/// use airbag::backends::Opsgenie;
+///
+/// let backend = Opsgenie::builder().api_key("some-api-key").region("eu").build();
+/// ```
+#[derive(typed_builder::TypedBuilder)]
+pub struct Opsgenie {
+    #[builder(setter(into))]
+    api_key: String,
+
+    #[builder(default = "us".into(), setter(into))]
+    region: String,
+
+    #[builder(default, setter(strip_option))]
+    base_url: Option<String>,
+
+    /// Tags added to every alert sent through this backend
+    #[builder(default, setter(into))]
+    tags: Vec<String>,
+
+    #[builder(default)]
+    retry_policy: RetryPolicy,
+}
+
+impl_http_backend!(Opsgenie);
+
+impl Opsgenie {
+    fn request(&self, alert: &crate::alert::Alert) -> anyhow::Result<HttpRequest> {
+        let mut url =
+            Url::parse(&self.base_url.as_ref().cloned().unwrap_or_else(
+                || match self.region.as_str() {
+                    "us" => "https://api.opsgenie.com".to_owned(),
+                    region => format!("https://api.{region}.opsgenie.com"),
+                },
+            ))
+            .context("Cannot parse URL")?;
+
+        let AlertMeta {
+            title,
+            dedup_key,
+            description,
+            action,
+            ..
+        } = alert.meta();
+
+        let json = match action {
+            Action::Trigger => {
+                url.set_path("/v2/alerts");
+
+                let priority = alert.meta().effective_priority();
+
+                let mut tags = self.tags.clone();
+                let mut details = serde_json::Map::new();
+                for (name, value) in alert.get_fields().as_object().into_iter().flatten() {
+                    match (name.as_str(), value) {
+                        ("tags", serde_json::Value::Array(values)) => {
+                            tags.extend(values.iter().map(field_text))
+                        }
+                        // Opsgenie details are string-to-string mappings
+                        (_, value) => {
+                            details.insert(name.clone(), json!(field_text(value)));
+                        }
+                    }
+                }
+
+                let mut json = json!({
+                    "message": truncate(title.as_deref().unwrap_or("Airbag alert"), MAX_MESSAGE_LENGTH),
+                    "priority": match priority {
+                        Priority::P1 => "P1",
+                        Priority::P2 => "P2",
+                        Priority::P3 => "P3",
+                        Priority::P4 => "P4",
+                        Priority::P5 => "P5",
+                    },
+                    "source": "airbag",
+                });
+                if let Some(description) = description {
+                    json["description"] = json!(truncate(description, MAX_DESCRIPTION_LENGTH));
+                }
+                if let Some(dedup_key) = dedup_key {
+                    json["alias"] = json!(dedup_key);
+                }
+                if !tags.is_empty() {
+                    json["tags"] = json!(tags);
+                }
+                if !details.is_empty() {
+                    json["details"] = details.into();
+                }
+                json
+            }
+            Action::Acknowledge | Action::Resolve => {
+                let alias = dedup_key
+                    .as_deref()
+                    .context("Opsgenie requires a dedup key to acknowledge or close alerts")
+                    .map_err(super::permanent)?;
+                url.path_segments_mut()
+                    .map_err(|_| anyhow::format_err!("Cannot use base URL"))?
+                    .clear()
+                    .extend([
+                        "v2",
+                        "alerts",
+                        alias,
+                        if *action == Action::Resolve {
+                            "close"
+                        } else {
+                            "acknowledge"
+                        },
+                    ]);
+                url.query_pairs_mut().append_pair("identifierType", "alias");
+                json!({ "source": "airbag" })
+            }
+        };
+
+        log::debug!("Opsgenie event: {json:?}");
+
+        log::debug!("Sending alert #{} to Opsgenie", alert.id());
+        Ok(HttpRequest::new(Method::POST, url)
+            .header("Authorization", format!("GenieKey {}", self.api_key))
+            .json(json))
+    }
+}
@@ -2,12 +2,20 @@ use anyhow::Context;
 use reqwest::Url;
 use serde_json::json;
 
-use crate::{alert::AlertMeta, utils::json_set_if_not_present};
+use super::RetryPolicy;
+use crate::{
+    alert::{Action, AlertMeta},
+    http::HttpRequest,
+    utils::json_set_if_not_present,
+};
 
 const MAX_SUMMARY_LENGTH: usize = 1000;
 
 /// The `PagerDuty` struct implements a backend for the [PagerDuty](https://pagerduty.com) service, and uses its
-/// "Events v2" integration API to emit alerts
+/// "Events v2" integration API to emit alerts.
+///
+/// All alert [actions](crate::alert::Action) are supported, and map to the `trigger`, `acknowledge` and `resolve`
+/// event actions respectively
 #[derive(typed_builder::TypedBuilder)]
 pub struct PagerDuty {
     #[builder(setter(into))]
@@ -15,10 +23,15 @@ pub struct PagerDuty {
 
     #[builder(default, setter(strip_option))]
     base_url: Option<String>,
+
+    #[builder(default)]
+    retry_policy: RetryPolicy,
 }
 
-impl super::Backend for PagerDuty {
-    fn send(&mut self, alert: crate::alert::Alert) -> anyhow::Result<()> {
+impl_http_backend!(PagerDuty);
+
+impl PagerDuty {
+    fn request(&self, alert: &crate::alert::Alert) -> anyhow::Result<HttpRequest> {
         let url = Url::parse(
             self.base_url
                 .as_deref()
@@ -28,66 +41,83 @@ impl super::Backend for PagerDuty {
         .join("/v2/enqueue")
         .unwrap();
 
-        let mut json = alert.as_json().clone();
-
-        if json["payload"]["source"].is_null() {
-            json["payload"]["source"] = json!("airbag");
-        }
-
-        json["routing_key"] = serde_json::json!(self.token.clone());
-
         let AlertMeta {
             title,
             dedup_key,
             severity,
             priority,
             description,
+            action,
+            ..
         } = alert.meta();
 
-        let severity =
-            severity.unwrap_or_else(|| priority.unwrap_or(crate::alert::Priority::P1).into());
-
-        json_set_if_not_present(
-            &mut json,
-            &["payload", "severity"],
-            match severity {
-                crate::alert::Severity::Critical => "critical",
-                crate::alert::Severity::Error => "error",
-                crate::alert::Severity::Warning => "warning",
-                crate::alert::Severity::Info => "info",
-            },
-        );
-
-        json_set_if_not_present(
-            &mut json,
-            &["payload", "summary"],
-            title.as_deref().unwrap_or("Airbag alert"),
-        );
-
-        if let Some(description) = description {
-            json_set_if_not_present(&mut json, &["payload", "custom_details"], description);
-        }
-
-        if let Some(dedup_key) = dedup_key {
-            json["dedup_key"] = json!(dedup_key);
-        }
+        let mut json = match action {
+            Action::Trigger => alert.as_json().clone(),
+            Action::Acknowledge | Action::Resolve => {
+                // acknowledge and resolve events only refer to an existing incident, and do not carry a payload
+                json!({
+                    "dedup_key": dedup_key
+                        .as_deref()
+                        .context("PagerDuty requires a dedup key to acknowledge or resolve incidents")
+                        .map_err(super::permanent)?
+                })
+            }
+        };
 
-        json["event_action"] = json!("trigger");
-
-        if json["payload"]["summary"].as_str().unwrap_or("").len() > MAX_SUMMARY_LENGTH {
-            let mut summary = json["payload"]["summary"].take().as_str().unwrap()
-                [..MAX_SUMMARY_LENGTH - 3]
-                .to_owned();
-            summary.push_str("...");
+        json["routing_key"] = serde_json::json!(self.token.clone());
 
-            json["payload"]["summary"] = json!(summary);
+        json["event_action"] = json!(match action {
+            Action::Trigger => "trigger",
+            Action::Acknowledge => "acknowledge",
+            Action::Resolve => "resolve",
+        });
+
+        if *action == Action::Trigger {
+            if json["payload"]["source"].is_null() {
+                json["payload"]["source"] = json!("airbag");
+            }
+
+            let severity =
+                severity.unwrap_or_else(|| priority.unwrap_or(crate::alert::Priority::P1).into());
+
+            json_set_if_not_present(
+                &mut json,
+                &["payload", "severity"],
+                match severity {
+                    crate::alert::Severity::Critical => "critical",
+                    crate::alert::Severity::Error => "error",
+                    crate::alert::Severity::Warning => "warning",
+                    crate::alert::Severity::Info => "info",
+                },
+            );
+
+            json_set_if_not_present(
+                &mut json,
+                &["payload", "summary"],
+                title.as_deref().unwrap_or("Airbag alert"),
+            );
+
+            if let Some(description) = description {
+                json_set_if_not_present(&mut json, &["payload", "custom_details"], description);
+            }
+
+            if let Some(dedup_key) = dedup_key {
+                json["dedup_key"] = json!(dedup_key);
+            }
+
+            if json["payload"]["summary"].as_str().unwrap_or("").len() > MAX_SUMMARY_LENGTH {
+                let mut summary = json["payload"]["summary"].take().as_str().unwrap()
+                    [..MAX_SUMMARY_LENGTH - 3]
+                    .to_owned();
+                summary.push_str("...");
+
+                json["payload"]["summary"] = json!(summary);
+            }
         }
 
         log::debug!("Pagerduty event: {json:?}");
 
         log::debug!("Sending alert #{} to PagerDuty", alert.id());
-        crate::utils::http_retry(|client| client.post(url.clone()).json(&json))?;
-
-        Ok(())
+        Ok(HttpRequest::post_json(url, json))
     }
 }
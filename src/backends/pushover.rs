@@ -0,0 +1,152 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use reqwest::{Method, Url};
+
+use super::RetryPolicy;
+use crate::{
+    alert::{Action, AlertMeta, Severity},
+    http::HttpRequest,
+    utils::{plain_text_message, truncate},
+};
+
+const MAX_TITLE_LENGTH: usize = 250;
+const MAX_MESSAGE_LENGTH: usize = 1024;
+const MIN_EMERGENCY_RETRY: Duration = Duration::from_secs(30);
+const MAX_EMERGENCY_EXPIRE: Duration = Duration::from_secs(3 * 60 * 60);
+
+/// The `Pushover` struct implements a backend sending alerts as push notifications through
+/// [Pushover](https://pushover.net), using an application token and a user or group key.
+///
+/// Alert severities map to Pushover's priorities. [Severity::Critical] alerts are sent with emergency priority,
+/// repeating the notification until it is acknowledged on a device or until it expires, and are tagged with the
+/// alert's dedup key. Resolving an alert cancels the repetition of the emergency notifications carrying its dedup
+/// key. Acknowledging alerts is not supported, as Pushover only allows acknowledging from its apps.
+///
+/// ```
+/// use airbag::backends::Pushover;
+///
+/// let backend = Pushover::builder()
+///     .app_token("some-app-token")
+///     .user_key("some-user-key")
+///     .build();
+/// ```
+#[derive(typed_builder::TypedBuilder)]
+pub struct Pushover {
+    #[builder(setter(into))]
+    app_token: String,
+
+    #[builder(setter(into))]
+    user_key: String,
+
+    #[builder(default, setter(strip_option))]
+    base_url: Option<String>,
+
+    /// How often emergency notifications are repeated until acknowledged. Pushover requires at least 30 seconds, so
+    /// shorter intervals are raised to it
+    #[builder(
+        default = Duration::from_secs(60),
+        setter(transform = |retry: Duration| retry.max(MIN_EMERGENCY_RETRY))
+    )]
+    emergency_retry: Duration,
+
+    /// How long emergency notifications are repeated for. Pushover allows up to 3 hours, so longer durations are
+    /// lowered to it
+    #[builder(
+        default = Duration::from_secs(60 * 60),
+        setter(transform = |expire: Duration| expire.min(MAX_EMERGENCY_EXPIRE))
+    )]
+    emergency_expire: Duration,
+
+    #[builder(default)]
+    retry_policy: RetryPolicy,
+}
+
+impl_http_backend!(Pushover);
+
+impl Pushover {
+    fn request(&self, alert: &crate::alert::Alert) -> anyhow::Result<HttpRequest> {
+        let base_url = Url::parse(
+            self.base_url
+                .as_deref()
+                .unwrap_or("https://api.pushover.net"),
+        )
+        .context("Cannot parse URL")?;
+
+        let AlertMeta {
+            title,
+            dedup_key,
+            action,
+            ..
+        } = alert.meta();
+
+        let (url, form) = match action {
+            Action::Trigger => {
+                let severity = alert.meta().effective_severity();
+                let title = title.as_deref().unwrap_or("Airbag alert");
+
+                let message = plain_text_message(alert, None);
+
+                let mut form = vec![
+                    ("token".to_owned(), self.app_token.clone()),
+                    ("user".to_owned(), self.user_key.clone()),
+                    ("title".to_owned(), truncate(title, MAX_TITLE_LENGTH)),
+                    (
+                        "message".to_owned(),
+                        truncate(
+                            if message.is_empty() { title } else { &message },
+                            MAX_MESSAGE_LENGTH,
+                        ),
+                    ),
+                    (
+                        "priority".to_owned(),
+                        match severity {
+                            Severity::Critical => "2",
+                            Severity::Error => "1",
+                            Severity::Warning => "0",
+                            Severity::Info => "-1",
+                        }
+                        .to_owned(),
+                    ),
+                ];
+                if let Severity::Critical = severity {
+                    form.push((
+                        "retry".to_owned(),
+                        self.emergency_retry.as_secs().to_string(),
+                    ));
+                    form.push((
+                        "expire".to_owned(),
+                        self.emergency_expire.as_secs().to_string(),
+                    ));
+                    if let Some(dedup_key) = dedup_key {
+                        form.push(("tags".to_owned(), dedup_key.clone()));
+                    }
+                }
+                (base_url.join("/1/messages.json").unwrap(), form)
+            }
+            Action::Resolve => {
+                let dedup_key = dedup_key
+                    .as_deref()
+                    .context("Pushover requires a dedup key to cancel emergency notifications")
+                    .map_err(super::permanent)?;
+                let mut url = base_url;
+                url.path_segments_mut()
+                    .map_err(|_| anyhow::format_err!("Cannot use base URL"))?
+                    .clear()
+                    .extend([
+                        "1",
+                        "receipts",
+                        "cancel_by_tag",
+                        &format!("{dedup_key}.json"),
+                    ]);
+                (url, vec![("token".to_owned(), self.app_token.clone())])
+            }
+            Action::Acknowledge => {
+                bail_permanent!("Pushover does not support acknowledging notifications")
+            }
+        };
+
+        log::debug!("Sending alert #{} to Pushover", alert.id());
+        Ok(HttpRequest::new(Method::POST, url).form(form))
+    }
+}
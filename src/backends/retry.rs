@@ -0,0 +1,180 @@
+use std::time::Duration;
+
+/// Controls how HTTP-based backends retry failed requests.
+///
+/// Requests are retried on connection errors, server-side (5xx) errors and rate limiting (HTTP 429), in which case
+/// the `Retry-After` header is honored up to the maximal delay. Other client-side errors are not retried.
+///
+/// The default policy retries indefinitely, with an exponential backoff starting at 500ms and capped at 30 seconds.
+/// Since a backend delivers alerts one at a time, bounding the retries is recommended for backends that should not
+/// hold back subsequent alerts for long:
+///
+/// ```
+/// use std::time::Duration;
+/// use airbag::backends::{PagerDuty, RetryPolicy};
+///
+/// let backend = PagerDuty::builder()
+///     .token("your token")
+///     .retry_policy(
+///         RetryPolicy::builder()
+///             .max_attempts(5)
+///             .max_elapsed(Duration::from_secs(60))
+///             .jitter(true)
+///             .build(),
+///     )
+///     .build();
+/// ```
+#[derive(Clone, Debug, typed_builder::TypedBuilder)]
+pub struct RetryPolicy {
+    /// The maximal number of attempts, including the first one
+    #[builder(default, setter(strip_option))]
+    max_attempts: Option<u32>,
+
+    /// The maximal time to spend on a single request, including retries
+    #[builder(default, setter(strip_option))]
+    max_elapsed: Option<Duration>,
+
+    /// The delay before the first retry, doubled on each subsequent retry
+    #[builder(default = Duration::from_millis(500))]
+    base_delay: Duration,
+
+    /// The maximal delay between consecutive attempts, also capping delays requested through `Retry-After`
+    #[builder(default = Duration::from_secs(30))]
+    max_delay: Duration,
+
+    /// Randomizes each delay to between half and the full backoff duration, to avoid retrying in lockstep with
+    /// other clients
+    #[builder(default)]
+    jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl RetryPolicy {
+    /// Returns the delay before the next attempt, or `None` if the request should not be retried anymore
+    pub(crate) fn next_delay(
+        &self,
+        attempts: u32,
+        elapsed: Duration,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        if self.max_attempts.is_some_and(|max| attempts >= max) {
+            return None;
+        }
+
+        // a server asking to wait longer than the remaining time budget will not accept the request in time anyway
+        let requested = retry_after.unwrap_or_default();
+        let delay = retry_after
+            .map(|retry_after| retry_after.min(self.max_delay))
+            .unwrap_or_else(|| self.backoff(attempts));
+
+        if self
+            .max_elapsed
+            .is_some_and(|max_elapsed| elapsed + delay.max(requested) > max_elapsed)
+        {
+            return None;
+        }
+        Some(delay)
+    }
+
+    fn backoff(&self, attempts: u32) -> Duration {
+        let delay = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(attempts.saturating_sub(1)))
+            .min(self.max_delay);
+        if self.jitter {
+            delay.mul_f64(0.5 + fastrand::f64() / 2.0)
+        } else {
+            delay
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RetryPolicy;
+
+    #[test]
+    fn test_default_policy_backoff() {
+        let policy = RetryPolicy::default();
+
+        assert_eq!(
+            policy.next_delay(1, Duration::ZERO, None),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            policy.next_delay(3, Duration::ZERO, None),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            policy.next_delay(1000, Duration::from_secs(86400), None),
+            Some(Duration::from_secs(30))
+        );
+    }
+
+    #[test]
+    fn test_max_attempts() {
+        let policy = RetryPolicy::builder().max_attempts(3).build();
+
+        assert!(policy.next_delay(2, Duration::ZERO, None).is_some());
+        assert!(policy.next_delay(3, Duration::ZERO, None).is_none());
+    }
+
+    #[test]
+    fn test_max_elapsed() {
+        let policy = RetryPolicy::builder()
+            .max_elapsed(Duration::from_secs(10))
+            .build();
+
+        assert!(policy.next_delay(1, Duration::from_secs(9), None).is_some());
+        assert!(policy
+            .next_delay(1, Duration::from_secs(10), None)
+            .is_none());
+        assert!(policy
+            .next_delay(1, Duration::ZERO, Some(Duration::from_secs(11)))
+            .is_none());
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let policy = RetryPolicy::default();
+
+        assert_eq!(
+            policy.next_delay(1, Duration::ZERO, Some(Duration::from_secs(10))),
+            Some(Duration::from_secs(10))
+        );
+        // delays requested by the server are capped by the maximal delay
+        assert_eq!(
+            policy.next_delay(1, Duration::ZERO, Some(Duration::from_secs(86400))),
+            Some(Duration::from_secs(30))
+        );
+
+        // requested delays exceeding the remaining time budget stop the retries, even when capped
+        let policy = RetryPolicy::builder()
+            .max_elapsed(Duration::from_secs(60))
+            .build();
+        assert_eq!(
+            policy.next_delay(1, Duration::ZERO, Some(Duration::from_secs(50))),
+            Some(Duration::from_secs(30))
+        );
+        assert!(policy
+            .next_delay(1, Duration::ZERO, Some(Duration::from_secs(120)))
+            .is_none());
+    }
+
+    #[test]
+    fn test_jitter() {
+        let policy = RetryPolicy::builder().jitter(true).build();
+
+        for _ in 0..100 {
+            let delay = policy.next_delay(2, Duration::ZERO, None).unwrap();
+            assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_secs(1));
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use anyhow::Context;
+
+use super::Backend;
+use crate::{
+    alert::{Priority, Severity},
+    Alert,
+};
+
+/// A predicate over alerts, selecting the alerts delivered through a [Router] route.
+///
+/// Rules can be combined with [Rule::and], [Rule::or] and [Rule::not]:
+///
+/// ```
+/// use airbag::{alert::Severity, backends::router::Rule};
+///
+/// let rule = Rule::severity_at_least(Severity::Error).and(Rule::title_matches("^Panic").unwrap().not());
+/// ```
+pub struct Rule {
+    predicate: Box<dyn Fn(&Alert) -> bool + Send>,
+}
+
+impl Rule {
+    /// Matches alerts satisfying an arbitrary predicate
+    pub fn new(predicate: impl Fn(&Alert) -> bool + Send + 'static) -> Self {
+        Self {
+            predicate: Box::new(predicate),
+        }
+    }
+
+    /// Matches all alerts
+    pub fn any() -> Self {
+        Self::new(|_| true)
+    }
+
+    /// Matches alerts with the given severity. The severity of alerts which only have a priority is derived from it,
+    /// and alerts with neither are considered errors
+    pub fn severity(severity: Severity) -> Self {
+        Self::new(move |alert| {
+            severity_rank(alert.meta().effective_severity()) == severity_rank(severity)
+        })
+    }
+
+    /// Matches alerts with the given severity or a more severe one
+    pub fn severity_at_least(severity: Severity) -> Self {
+        Self::new(move |alert| {
+            severity_rank(alert.meta().effective_severity()) <= severity_rank(severity)
+        })
+    }
+
+    /// Matches alerts with the given priority. The priority of alerts which only have a severity is derived from it,
+    /// and alerts with neither are considered errors
+    pub fn priority(priority: Priority) -> Self {
+        Self::new(move |alert| {
+            priority_rank(alert.meta().effective_priority()) == priority_rank(priority)
+        })
+    }
+
+    /// Matches alerts with the given priority or a higher one, e.g. P1 and P2 for [Priority::P2]
+    pub fn priority_at_least(priority: Priority) -> Self {
+        Self::new(move |alert| {
+            priority_rank(alert.meta().effective_priority()) <= priority_rank(priority)
+        })
+    }
+
+    /// Matches alerts whose title matches the given regular expression
+    pub fn title_matches(pattern: &str) -> anyhow::Result<Self> {
+        let regex = regex::Regex::new(pattern).context("Invalid title pattern")?;
+        Ok(Self::new(move |alert| {
+            alert
+                .title()
+                .as_deref()
+                .is_some_and(|title| regex.is_match(title))
+        }))
+    }
+
+    /// Matches alerts whose [source location](crate::Alert::location) lies in a file matching the given glob, using
+    /// the same syntax as [CodeOwners](super::CodeOwners) patterns, e.g. `src/billing/**`
+    pub fn source_file_matches(pattern: &str) -> anyhow::Result<Self> {
+        let matcher = super::codeowners::compile_pattern(pattern)?;
+        Ok(Self::new(move |alert| {
+            alert
+                .location()
+                .is_some_and(|location| matcher.is_match(&location.file))
+        }))
+    }
+
+    /// Matches alerts having the given field
+    pub fn field_exists(name: impl Into<String>) -> Self {
+        let name = name.into();
+        Self::new(move |alert| alert.get_field(&name).is_some())
+    }
+
+    /// Matches alerts in which the given field holds the given value
+    pub fn field_equals(name: impl Into<String>, value: impl serde::Serialize) -> Self {
+        let name = name.into();
+        let value = serde_json::json!(value);
+        Self::new(move |alert| alert.get_field(&name) == Some(&value))
+    }
+
+    /// Matches alerts whose dedup key starts with the given prefix
+    pub fn dedup_key_prefix(prefix: impl Into<String>) -> Self {
+        let prefix = prefix.into();
+        Self::new(move |alert| {
+            alert
+                .dedup_key()
+                .as_deref()
+                .is_some_and(|dedup_key| dedup_key.starts_with(&prefix))
+        })
+    }
+
+    /// Matches alerts matched by both this rule and the given one
+    pub fn and(self, other: Rule) -> Self {
+        Self::new(move |alert| self.matches(alert) && other.matches(alert))
+    }
+
+    /// Matches alerts matched by either this rule or the given one
+    pub fn or(self, other: Rule) -> Self {
+        Self::new(move |alert| self.matches(alert) || other.matches(alert))
+    }
+
+    /// Matches alerts not matched by this rule
+    #[allow(clippy::should_implement_trait)]
+    pub fn not(self) -> Self {
+        Self::new(move |alert| !self.matches(alert))
+    }
+
+    pub fn matches(&self, alert: &Alert) -> bool {
+        (self.predicate)(alert)
+    }
+}
+
+fn severity_rank(severity: Severity) -> u8 {
+    match severity {
+        Severity::Critical => 0,
+        Severity::Error => 1,
+        Severity::Warning => 2,
+        Severity::Info => 3,
+    }
+}
+
+fn priority_rank(priority: Priority) -> u8 {
+    match priority {
+        Priority::P1 => 0,
+        Priority::P2 => 1,
+        Priority::P3 => 2,
+        Priority::P4 => 3,
+        Priority::P5 => 4,
+    }
+}
+
+struct Route {
+    rule: Rule,
+    name: &'static str,
+    backend: Box<dyn Backend + Send>,
+}
+
+impl Route {
+    fn new<B: Backend + Send + 'static>(rule: Rule, backend: B) -> Self {
+        Self {
+            rule,
+            name: std::any::type_name::<B>(),
+            backend: Box::new(backend),
+        }
+    }
+}
+
+/// The `Router` struct implements a backend delivering alerts through different backends according to [rules](Rule).
+///
+/// Routes are evaluated in order, and alerts are delivered through the first matching route, or through every
+/// matching route when [Router::all_matches] is enabled. Alerts not matching any route are delivered through the
+/// fallback backend, if one is set, and fail otherwise.
+///
+/// ```
+/// use airbag::{
+///     alert::Severity,
+///     backends::{router::Rule, PagerDuty, Router, Slack},
+/// };
+///
+/// let backend = Router::new()
+///     .route(
+///         Rule::severity_at_least(Severity::Error),
+///         PagerDuty::builder().token("your token").build(),
+///     )
+///     .fallback(Slack::builder().webhook_url("https://hooks.slack.com/services/T000/B000/XXXX").build());
+/// ```
+#[derive(Default)]
+pub struct Router {
+    routes: Vec<Route>,
+    fallback: Option<Route>,
+    all_matches: bool,
+}
+
+impl Router {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a route, delivering the alerts matching the given rule through the given backend
+    pub fn route<B: Backend + Send + 'static>(mut self, rule: Rule, backend: B) -> Self {
+        self.routes.push(Route::new(rule, backend));
+        self
+    }
+
+    /// Sets the backend delivering alerts not matching any other route
+    pub fn fallback<B: Backend + Send + 'static>(mut self, backend: B) -> Self {
+        self.fallback = Some(Route::new(Rule::any(), backend));
+        self
+    }
+
+    /// Controls whether alerts are delivered through every matching route, rather than the first one only. The
+    /// fallback backend is used only if no route matched
+    pub fn all_matches(mut self, all_matches: bool) -> Self {
+        self.all_matches = all_matches;
+        self
+    }
+}
+
+impl Backend for Router {
+    fn send(&mut self, alert: Alert) -> anyhow::Result<()> {
+        let mut routes = self
+            .routes
+            .iter_mut()
+            .filter(|route| route.rule.matches(&alert))
+            .collect::<Vec<_>>();
+        if routes.is_empty() {
+            routes.extend(self.fallback.as_mut());
+        }
+        if routes.is_empty() {
+            bail_permanent!("Alert did not match any route");
+        }
+        if !self.all_matches {
+            routes.truncate(1);
+        }
+
+        let total = routes.len();
+        let mut errors = Vec::new();
+        for route in routes {
+            log::debug!("Routing alert #{} to {}", alert.id(), route.name);
+            if let Err(e) = route.backend.send(alert.clone()) {
+                errors.push(format!("{}: {e:#}", route.name));
+            }
+        }
+
+        if !errors.is_empty() {
+            anyhow::bail!(
+                "Failed sending alert to {} of {total} routes: {}",
+                errors.len(),
+                errors.join("; ")
+            );
+        }
+        Ok(())
+    }
+
+    fn on_start(&mut self) {
+        for route in self.routes.iter_mut().chain(self.fallback.as_mut()) {
+            route.backend.on_start();
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use anyhow::Context;
+use reqwest::Url;
+use serde_json::json;
+
+use super::RetryPolicy;
+use crate::{
+    alert::{Action, AlertMeta, Severity},
+    http::HttpRequest,
+    utils::{field_text, truncate},
+};
+
+const MAX_HEADER_LENGTH: usize = 150;
+const MAX_SECTION_LENGTH: usize = 3000;
+const MAX_FIELD_LENGTH: usize = 2000;
+const MAX_FIELDS_PER_SECTION: usize = 10;
+
+pub(super) const ACKNOWLEDGED_COLOR: &str = "#9e9e9e";
+pub(super) const RESOLVED_COLOR: &str = "#2eb67d";
+
+/// The `Slack` struct implements a backend posting alerts to a Slack channel through an
+/// [incoming webhook](https://api.slack.com/messaging/webhooks).
+///
+/// Alerts are rendered as [Block Kit](https://api.slack.com/block-kit) messages inside an attachment colored by the
+/// alert's severity, showing the title, the description in a code block, the alert's fields and its dedup key.
+///
+/// Slack has no notion of incidents, so acknowledging and resolving alerts post a follow-up message referring to the
+/// original alert's dedup key.
+///
+/// ```
+/// use airbag::backends::Slack;
+///
+/// let backend = Slack::builder()
+///     .webhook_url("https://hooks.slack.com/services/T000/B000/XXXX")
+///     .build();
+/// ```
+#[derive(typed_builder::TypedBuilder)]
+pub struct Slack {
+    #[builder(setter(into))]
+    webhook_url: String,
+
+    /// Overrides the webhook's default channel, where supported by the webhook
+    #[builder(default, setter(strip_option, into))]
+    channel: Option<String>,
+
+    #[builder(default)]
+    retry_policy: RetryPolicy,
+}
+
+impl_http_backend!(Slack);
+
+impl Slack {
+    fn request(&self, alert: &crate::alert::Alert) -> anyhow::Result<HttpRequest> {
+        let url = Url::parse(&self.webhook_url).context("Cannot parse URL")?;
+
+        let AlertMeta {
+            title,
+            description,
+            dedup_key,
+            action,
+            ..
+        } = alert.meta();
+
+        let severity = alert.meta().effective_severity();
+        let title = title.as_deref().unwrap_or("Airbag alert");
+
+        let (text, color) = match action {
+            Action::Trigger => (title.to_owned(), color(severity)),
+            Action::Acknowledge => (format!("Acknowledged: {title}"), ACKNOWLEDGED_COLOR),
+            Action::Resolve => (format!("Resolved: {title}"), RESOLVED_COLOR),
+        };
+
+        let mut blocks = vec![json!({
+            "type": "header",
+            "text": {"type": "plain_text", "text": truncate(&text, MAX_HEADER_LENGTH)},
+        })];
+
+        if let Some(description) = description {
+            blocks.push(json!({
+                "type": "section",
+                "text": {
+                    "type": "mrkdwn",
+                    "text": format!("```{}```", truncate_escaped(&escape_code_block(&escape_mrkdwn(description)), MAX_SECTION_LENGTH - 6)),
+                },
+            }));
+        }
+
+        let fields = alert
+            .get_fields()
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(name, value)| {
+                let text = format!(
+                    "*{}*\n{}",
+                    escape_mrkdwn(name),
+                    escape_mrkdwn(&field_text(value))
+                );
+                json!({"type": "mrkdwn", "text": truncate_escaped(&text, MAX_FIELD_LENGTH)})
+            })
+            .collect::<Vec<_>>();
+        for chunk in fields.chunks(MAX_FIELDS_PER_SECTION) {
+            blocks.push(json!({"type": "section", "fields": chunk}));
+        }
+
+        if let Some(dedup_key) = dedup_key {
+            let text = format!(
+                "Dedup key: `{}`",
+                escape_code_block(&escape_mrkdwn(dedup_key))
+            );
+            blocks.push(json!({
+                "type": "context",
+                "elements": [{"type": "mrkdwn", "text": truncate_escaped(&text, MAX_SECTION_LENGTH)}],
+            }));
+        }
+
+        let mut json = json!({
+            "text": text,
+            "attachments": [{"color": color, "blocks": blocks}],
+        });
+        if let Some(channel) = &self.channel {
+            json["channel"] = json!(channel);
+        }
+
+        log::debug!("Slack message: {json:?}");
+
+        log::debug!("Sending alert #{} to Slack", alert.id());
+        Ok(HttpRequest::post_json(url, json))
+    }
+}
+
+/// The attachment color of triggered alerts, shared by the chat backends
+pub(super) fn color(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Critical => "#a30200",
+        Severity::Error => "#e01e5a",
+        Severity::Warning => "#ecb22e",
+        Severity::Info => "#36c5f0",
+    }
+}
+
+/// Replaces backticks, which mrkdwn cannot escape, so the description cannot close its code block early
+pub(super) fn escape_code_block(s: &str) -> String {
+    s.replace('`', "\u{2cb}")
+}
+
+/// Escapes the characters mrkdwn reserves for links and mentions
+fn escape_mrkdwn(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// Truncates escaped mrkdwn, without leaving a partial escape sequence before the ellipsis
+fn truncate_escaped(s: &str, max_chars: usize) -> String {
+    let truncated = truncate(s, max_chars);
+    if truncated == s {
+        return truncated;
+    }
+    let kept = truncated.strip_suffix("...").unwrap_or(&truncated);
+    match kept.rfind('&') {
+        Some(index) if !kept[index..].contains(';') => format!("{}...", &kept[..index]),
+        _ => truncated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_truncate_escaped() {
+        use super::truncate_escaped;
+
+        assert_eq!(truncate_escaped("a &amp; b", 20), "a &amp; b");
+        assert_eq!(truncate_escaped("a &amp; b &lt; c", 12), "a &amp; b...");
+        assert_eq!(truncate_escaped("a &amp; b &lt; c", 14), "a &amp; b ...");
+        assert_eq!(truncate_escaped("abcdef &amp; b", 11), "abcdef ...");
+    }
+}
@@ -0,0 +1,356 @@
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+use anyhow::Context;
+use parking_lot::RwLock;
+
+use super::{is_permanent, Backend};
+use crate::{alert::AlertMeta, Alert};
+
+const EXTENSION: &str = "json";
+const LOCK_EXTENSION: &str = "lock";
+const FAILED_EXTENSION: &str = "failed";
+const MAX_REPLAY_ATTEMPTS: u32 = 5;
+
+static INSTANCE_ID: AtomicU64 = AtomicU64::new(0);
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SpooledAlert {
+    meta: AlertMeta,
+    fields: serde_json::Value,
+    /// The number of failed replays
+    #[serde(default)]
+    attempts: u32,
+}
+
+/// The `Spool` struct wraps a backend with a durable, on-disk spool, so that alerts survive crashes of the process
+/// sending them.
+///
+/// Each alert is written to its own file in the spool directory by the thread triggering it, before it is queued for
+/// delivery, and the file is deleted once the wrapped backend delivers it. Alerts left over in the spool, because
+/// their delivery failed or because the process terminated before delivering them, are replayed when the hub is next
+/// configured, before any new alert is sent. Since the spool is set up by the hub, it must be the backend the hub is
+/// configured with, rather than nested in another backend.
+///
+/// Alerts failing with errors no retry can fix, such as requests rejected by the service, are not kept. Alerts whose
+/// replay keeps failing are moved aside to `.failed` files after 5 attempts.
+///
+/// A spool directory can be shared by several hubs, including ones in other processes. Each hub holds a lock until it
+/// terminates, and only replays the leftovers of hubs whose lock was released, so that alerts still queued by a
+/// running hub are never sent twice.
+///
+/// ```
+/// use airbag::backends::{PagerDuty, Spool};
+///
+/// let backend = Spool::new(
+///     "/var/spool/myapp/airbag",
+///     PagerDuty::builder().token("your token").build(),
+/// );
+/// ```
+pub struct Spool<B> {
+    dir: PathBuf,
+    backend: B,
+}
+
+impl<B: Backend> Spool<B> {
+    pub fn new(dir: impl Into<PathBuf>, backend: B) -> Self {
+        Self {
+            dir: dir.into(),
+            backend,
+        }
+    }
+}
+
+impl<B: Backend> Backend for Spool<B> {
+    fn send(&mut self, alert: Alert) -> anyhow::Result<()> {
+        self.backend.send(alert)
+    }
+
+    fn on_start(&mut self) {
+        self.backend.on_start();
+    }
+
+    fn open_spool(&self) -> Option<SpoolHandle> {
+        match SpoolHandle::open(&self.dir) {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                // failing to spool should not prevent delivery
+                log::error!("Cannot open spool directory. Alerts will not be spooled: {e:?}");
+                None
+            }
+        }
+    }
+}
+
+/// A spool directory opened by a hub, through which the hub persists alerts until they are delivered. Returned by
+/// [Backend::open_spool]
+#[derive(Clone)]
+pub struct SpoolHandle(Arc<SpoolDir>);
+
+struct SpoolDir {
+    dir: PathBuf,
+    /// Identifies the files written by this hub, and the lock it holds
+    instance: String,
+    lock_path: PathBuf,
+    /// The lock held until the hub terminates. Alerts are written while holding it for reading, so that no alert is
+    /// written once it is released
+    lock: RwLock<Option<File>>,
+}
+
+impl SpoolDir {
+    fn release(&self) {
+        let mut lock = self.lock.write();
+        if lock.is_some() {
+            // the lock itself is released once the file is closed
+            let _ = std::fs::remove_file(&self.lock_path);
+            *lock = None;
+        }
+    }
+}
+
+impl Drop for SpoolDir {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+impl SpoolHandle {
+    fn open(dir: &Path) -> anyhow::Result<Self> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Cannot create spool directory {dir:?}"))?;
+
+        let instance = format!(
+            "{}-{}",
+            std::process::id(),
+            INSTANCE_ID.fetch_add(1, Ordering::Relaxed)
+        );
+        let lock_path = dir.join(&instance).with_extension(LOCK_EXTENSION);
+        let lock = File::create(&lock_path)
+            .with_context(|| format!("Cannot create spool lock {lock_path:?}"))?;
+        lock.try_lock()
+            .with_context(|| format!("Cannot lock spool lock {lock_path:?}"))?;
+
+        Ok(Self(Arc::new(SpoolDir {
+            dir: dir.to_owned(),
+            instance,
+            lock_path,
+            lock: RwLock::new(Some(lock)),
+        })))
+    }
+
+    /// Releases the spool's lock once its hub terminates, letting other hubs replay its leftovers
+    pub(crate) fn release(&self) {
+        self.0.release();
+    }
+
+    /// Persists an alert, returning the path of its file. Errors are logged rather than returned, as failing to spool
+    /// should not prevent delivery
+    pub(crate) fn write(&self, alert: &Alert) -> Option<PathBuf> {
+        self.try_write(alert)
+            .map_err(|e| log::error!("Cannot spool alert #{}: {e:?}", alert.id()))
+            .ok()
+    }
+
+    fn try_write(&self, alert: &Alert) -> anyhow::Result<PathBuf> {
+        let lock = self.0.lock.read();
+        if lock.is_none() {
+            anyhow::bail!("The hub has terminated");
+        }
+
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        // file names sort by creation time, so that leftovers are replayed in order
+        let name = format!("{timestamp:024}-{}-{}", self.0.instance, alert.id());
+        let path = self.0.dir.join(name).with_extension(EXTENSION);
+        write(
+            &path,
+            &SpooledAlert {
+                meta: alert.meta().clone(),
+                fields: alert.as_json().clone(),
+                attempts: 0,
+            },
+        )?;
+        Ok(path)
+    }
+
+    /// Deletes the file of a delivered alert
+    pub(crate) fn remove(&self, path: &Path) {
+        if let Err(e) = std::fs::remove_file(path).and_then(|_| sync_dir(&self.0.dir)) {
+            log::error!("Cannot remove spool file {path:?}: {e:?}");
+        }
+    }
+
+    /// Sends the alerts left over by hubs which are no longer running. Alerts which fail are kept for the next replay,
+    /// unless they can never be delivered or failed too many times
+    pub(crate) fn replay(&self, backend: &mut dyn Backend) -> anyhow::Result<()> {
+        let dir = &self.0.dir;
+        let entries = std::fs::read_dir(dir)
+            .with_context(|| format!("Cannot read spool directory {dir:?}"))?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .collect::<Vec<_>>();
+        let mut paths = entries
+            .iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == EXTENSION))
+            .collect::<Vec<_>>();
+        paths.sort();
+
+        // the locks of the hubs whose leftovers are replayed, held until replaying is done so that no other hub
+        // replays them as well
+        let mut owners: HashMap<String, Option<File>> = HashMap::new();
+        let mut replayed = 0;
+        for path in paths {
+            let Some(owner) = instance_of(path) else {
+                continue;
+            };
+            if owner == self.0.instance {
+                continue;
+            }
+            let lock = owners
+                .entry(owner.to_owned())
+                .or_insert_with(|| self.adopt(owner));
+            if lock.is_none() {
+                continue;
+            }
+
+            let mut spooled = match read(path) {
+                Ok(spooled) => spooled,
+                // already replayed by another hub
+                Err(e) if is_not_found(&e) => continue,
+                Err(e) => {
+                    log::error!("Discarding unreadable spool file {path:?}: {e:?}");
+                    let _ = std::fs::rename(path, path.with_extension("corrupt"));
+                    continue;
+                }
+            };
+            if replayed == 0 {
+                log::info!("Replaying spooled alerts from {dir:?}");
+            }
+            let alert = Alert::from_parts(spooled.meta.clone(), spooled.fields.clone());
+            match backend.send(alert) {
+                Ok(()) => self.remove(path),
+                Err(e) if is_permanent(&e) => {
+                    log::error!(
+                        "Discarding spooled alert {path:?}, which cannot be delivered: {e:?}"
+                    );
+                    self.remove(path);
+                }
+                Err(e) => {
+                    spooled.attempts += 1;
+                    if spooled.attempts >= MAX_REPLAY_ATTEMPTS {
+                        log::error!(
+                            "Giving up spooled alert {path:?} after {} attempts: {e:?}",
+                            spooled.attempts
+                        );
+                        let _ = std::fs::rename(path, path.with_extension(FAILED_EXTENSION));
+                    } else {
+                        log::error!("Failed replaying spooled alert {path:?}. It will be replayed on the next start: {e:?}");
+                        if let Err(e) = write(path, &spooled) {
+                            log::error!("Cannot update spool file {path:?}: {e:?}");
+                        }
+                    }
+                }
+            }
+            replayed += 1;
+        }
+
+        // clean up the locks of terminated hubs, including ones which left nothing to replay
+        for lock_path in entries
+            .iter()
+            .filter(|path| path.extension().is_some_and(|ext| ext == LOCK_EXTENSION))
+        {
+            let Some(owner) = lock_path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            if owner == self.0.instance {
+                continue;
+            }
+            let adopted = match owners.get(owner) {
+                Some(lock) => lock.is_some(),
+                None => self.adopt(owner).is_some(),
+            };
+            if adopted {
+                let _ = std::fs::remove_file(lock_path);
+            }
+        }
+        Ok(())
+    }
+
+    /// Takes over the lock of the hub which wrote the given files, returning `None` if that hub is still running
+    fn adopt(&self, owner: &str) -> Option<File> {
+        let lock_path = self.0.dir.join(owner).with_extension(LOCK_EXTENSION);
+        let lock = match File::options()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(&lock_path)
+        {
+            Ok(lock) => lock,
+            Err(e) => {
+                log::error!("Cannot open spool lock {lock_path:?}: {e:?}");
+                return None;
+            }
+        };
+        match lock.try_lock() {
+            Ok(()) => Some(lock),
+            Err(_) => {
+                log::debug!("Spool files of {owner} belong to a running hub. Skipping them");
+                None
+            }
+        }
+    }
+}
+
+/// Returns the instance which wrote a spool file, named `<timestamp>-<pid>-<instance>-<alert id>.json`
+fn instance_of(path: &Path) -> Option<&str> {
+    let stem = path.file_stem()?.to_str()?;
+    let (_, rest) = stem.split_once('-')?;
+    let (instance, _) = rest.rsplit_once('-')?;
+    Some(instance)
+}
+
+/// Writes a spool file atomically, so that replays never see it partially written
+fn write(path: &Path, spooled: &SpooledAlert) -> anyhow::Result<()> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let tmp_path = dir.join(format!(
+        ".{}.tmp",
+        path.file_stem().unwrap_or_default().to_string_lossy()
+    ));
+
+    let mut file = File::create(&tmp_path)
+        .with_context(|| format!("Cannot create spool file {tmp_path:?}"))?;
+    file.write_all(&serde_json::to_vec(spooled)?)
+        .and_then(|_| file.sync_all())
+        .with_context(|| format!("Cannot write spool file {tmp_path:?}"))?;
+    std::fs::rename(&tmp_path, path)
+        .and_then(|_| sync_dir(dir))
+        .with_context(|| format!("Cannot write spool file {path:?}"))
+}
+
+fn read(path: &Path) -> anyhow::Result<SpooledAlert> {
+    Ok(serde_json::from_slice(&std::fs::read(path)?)?)
+}
+
+fn is_not_found(e: &anyhow::Error) -> bool {
+    e.downcast_ref::<std::io::Error>()
+        .is_some_and(|e| e.kind() == std::io::ErrorKind::NotFound)
+}
+
+/// Makes the creation, renaming or removal of files in a directory durable. Only needed (and possible) on Unix
+fn sync_dir(dir: &Path) -> std::io::Result<()> {
+    #[cfg(unix)]
+    File::open(dir)?.sync_all()?;
+    #[cfg(not(unix))]
+    let _ = dir;
+    Ok(())
+}
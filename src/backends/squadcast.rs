@@ -2,11 +2,19 @@ use anyhow::Context;
 use reqwest::Url;
 use serde_json::json;
 
-use crate::{alert::AlertMeta, utils::json_set_if_not_present};
+use super::RetryPolicy;
+use crate::{
+    alert::{Action, AlertMeta},
+    http::HttpRequest,
+    utils::json_set_if_not_present,
+};
 
 /// The `SquadCast` struct implements a backend for the [SquadCast](https://squadcast.com) service.
 /// Configuration options include SquadCast's region name and webhook token.
 ///
+/// Resolving incidents is supported by sending alerts with [Action::Resolve] and the
+/// dedup key of the original alert. SquadCast's incident webhook does not support acknowledging incidents.
+///
 /// The webhook token is the end component of the incidents v2 webhook URL.
 ///
 /// For example, if your webhook URL is https://api.eu.squadcast.com/v2/incidents/api/7f550a9f4c44173a37664d938f1355f0f92a47a7,
@@ -27,10 +35,15 @@ pub struct SquadCast {
 
     #[builder(default, setter(strip_option))]
     base_url: Option<String>,
+
+    #[builder(default)]
+    retry_policy: RetryPolicy,
 }
 
-impl super::Backend for SquadCast {
-    fn send(&mut self, alert: crate::alert::Alert) -> anyhow::Result<()> {
+impl_http_backend!(SquadCast);
+
+impl SquadCast {
+    fn request(&self, alert: &crate::alert::Alert) -> anyhow::Result<HttpRequest> {
         let url = Url::parse(
             &self
                 .base_url
@@ -42,49 +55,62 @@ impl super::Backend for SquadCast {
         .join(&format!("/v2/incidents/api/{}", self.token))
         .unwrap();
 
-        let mut json = alert.as_json().clone();
-
         let AlertMeta {
             title,
             dedup_key,
-            severity,
-            priority,
             description,
+            action,
+            ..
         } = alert.meta();
 
-        json_set_if_not_present(
-            &mut json,
-            &["priority"],
-            match priority
-                .unwrap_or_else(|| severity.unwrap_or(crate::alert::Severity::Error).into())
-            {
-                crate::alert::Priority::P1 => "P1",
-                crate::alert::Priority::P2 => "P2",
-                crate::alert::Priority::P3 => "P3",
-                crate::alert::Priority::P4 => "P4",
-                crate::alert::Priority::P5 => "P5",
-            },
-        );
+        let json = match action {
+            Action::Trigger => {
+                let mut json = alert.as_json().clone();
+
+                json_set_if_not_present(
+                    &mut json,
+                    &["priority"],
+                    match alert.meta().effective_priority() {
+                        crate::alert::Priority::P1 => "P1",
+                        crate::alert::Priority::P2 => "P2",
+                        crate::alert::Priority::P3 => "P3",
+                        crate::alert::Priority::P4 => "P4",
+                        crate::alert::Priority::P5 => "P5",
+                    },
+                );
 
-        json_set_if_not_present(
-            &mut json,
-            &["message"],
-            title.as_deref().unwrap_or("Airbag alert"),
-        );
+                json_set_if_not_present(
+                    &mut json,
+                    &["message"],
+                    title.as_deref().unwrap_or("Airbag alert"),
+                );
 
-        if let Some(description) = description {
-            json_set_if_not_present(&mut json, &["description"], description);
-        }
+                if let Some(description) = description {
+                    json_set_if_not_present(&mut json, &["description"], description);
+                }
 
-        if let Some(dedup_key) = dedup_key {
-            json["dedup_key"] = json!(dedup_key);
-        }
+                if let Some(dedup_key) = dedup_key {
+                    json["dedup_key"] = json!(dedup_key);
+                    // SquadCast matches resolve events to incidents by their event id
+                    json_set_if_not_present(&mut json, &["event_id"], dedup_key);
+                }
+                json
+            }
+            Action::Resolve => json!({
+                "status": "resolve",
+                "event_id": dedup_key
+                    .as_deref()
+                    .context("SquadCast requires a dedup key to resolve incidents")
+                    .map_err(super::permanent)?,
+            }),
+            Action::Acknowledge => {
+                bail_permanent!("SquadCast does not support acknowledging incidents")
+            }
+        };
 
         log::debug!("Squadcast event: {json:?}");
 
         log::debug!("Sending alert #{} to SquadCast", alert.id());
-        crate::utils::http_retry(|client| client.post(url.clone()).json(&json))?;
-
-        Ok(())
+        Ok(HttpRequest::post_json(url, json))
     }
 }
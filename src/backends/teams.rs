@@ -0,0 +1,174 @@
+use anyhow::Context;
+use reqwest::Url;
+use serde_json::{json, Value};
+
+use super::RetryPolicy;
+use crate::{
+    alert::{Action, AlertMeta, Severity},
+    http::HttpRequest,
+    utils::{field_text, truncate},
+};
+
+const MAX_TITLE_LENGTH: usize = 256;
+const MAX_DESCRIPTION_LENGTH: usize = 5000;
+const MAX_FACT_TITLE_LENGTH: usize = 100;
+const MAX_FACT_VALUE_LENGTH: usize = 500;
+const MAX_FACTS: usize = 50;
+// Teams rejects messages larger than 28KB. The serialized card elements are kept within this budget, leaving room for
+// the message's own structure
+const MAX_CARD_BYTES: usize = 24_000;
+
+/// The `Teams` struct implements a backend posting alerts to Microsoft Teams, through either an incoming webhook or a
+/// workflow URL.
+///
+/// Alerts are rendered as [Adaptive Cards](https://adaptivecards.io), showing the title colored by the alert's
+/// severity, the description, and the alert's fields and dedup key as a fact set. Long texts are truncated, and fields
+/// are dropped once the card would exceed Teams' message size limit.
+///
+/// Teams has no notion of incidents, so acknowledging and resolving alerts post a follow-up card referring to the
+/// original alert's dedup key.
+///
+/// ```
+/// use airbag::backends::Teams;
+///
+/// let backend = Teams::builder()
+///     .webhook_url("https://example.webhook.office.com/webhookb2/some-id")
+///     .build();
+/// ```
+#[derive(typed_builder::TypedBuilder)]
+pub struct Teams {
+    #[builder(setter(into))]
+    webhook_url: String,
+
+    #[builder(default)]
+    retry_policy: RetryPolicy,
+}
+
+impl_http_backend!(Teams);
+
+impl Teams {
+    fn request(&self, alert: &crate::alert::Alert) -> anyhow::Result<HttpRequest> {
+        let url = Url::parse(&self.webhook_url).context("Cannot parse URL")?;
+
+        let AlertMeta {
+            title,
+            description,
+            dedup_key,
+            action,
+            ..
+        } = alert.meta();
+
+        let severity = alert.meta().effective_severity();
+        let title = title.as_deref().unwrap_or("Airbag alert");
+
+        let (title, color) = match action {
+            Action::Trigger => (
+                title.to_owned(),
+                match severity {
+                    Severity::Critical | Severity::Error => "Attention",
+                    Severity::Warning => "Warning",
+                    Severity::Info => "Accent",
+                },
+            ),
+            Action::Acknowledge => (format!("Acknowledged: {title}"), "Default"),
+            Action::Resolve => (format!("Resolved: {title}"), "Good"),
+        };
+
+        let mut body = vec![
+            json!({
+                "type": "TextBlock",
+                "text": truncate(&title, MAX_TITLE_LENGTH),
+                "size": "Large",
+                "weight": "Bolder",
+                "color": color,
+                "wrap": true,
+            }),
+            json!({
+                "type": "TextBlock",
+                "text": format!("Severity: {}", severity.as_str()),
+                "isSubtle": true,
+                "spacing": "None",
+            }),
+        ];
+
+        // the dedup key is accounted for up front, so that it is never dropped in favor of fields
+        let dedup_key_fact = dedup_key.as_ref().map(|dedup_key| {
+            json!({"title": "Dedup key", "value": truncate(dedup_key, MAX_FACT_VALUE_LENGTH)})
+        });
+        let mut budget = MAX_CARD_BYTES
+            - body.iter().map(element_size).sum::<usize>()
+            - dedup_key_fact.as_ref().map_or(0, element_size);
+
+        if let Some(description) = description {
+            let mut max_length = MAX_DESCRIPTION_LENGTH;
+            loop {
+                let block = json!({
+                    "type": "TextBlock",
+                    "text": truncate(description, max_length),
+                    "fontType": "Monospace",
+                    "wrap": true,
+                });
+                let size = element_size(&block);
+                if size <= budget {
+                    budget -= size;
+                    body.push(block);
+                    break;
+                }
+                // shrink the text in proportion to how much it exceeds the budget, as escaping and multi-byte
+                // characters make its size differ from its length
+                match (max_length * budget / size).min(max_length - 1) {
+                    length if length > 3 => max_length = length,
+                    _ => break,
+                }
+            }
+        }
+
+        let mut facts = Vec::new();
+        for (name, value) in alert
+            .get_fields()
+            .as_object()
+            .into_iter()
+            .flatten()
+            .take(MAX_FACTS)
+        {
+            let fact = json!({
+                "title": truncate(name, MAX_FACT_TITLE_LENGTH),
+                "value": truncate(&field_text(value), MAX_FACT_VALUE_LENGTH),
+            });
+            let size = element_size(&fact);
+            if size > budget {
+                break;
+            }
+            budget -= size;
+            facts.push(fact);
+        }
+        facts.extend(dedup_key_fact);
+        if !facts.is_empty() {
+            body.push(json!({"type": "FactSet", "facts": facts}));
+        }
+
+        let json = json!({
+            "type": "message",
+            "attachments": [{
+                "contentType": "application/vnd.microsoft.card.adaptive",
+                "content": {
+                    "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                    "type": "AdaptiveCard",
+                    "version": "1.4",
+                    "body": body,
+                    "msteams": {"width": "Full"},
+                },
+            }],
+        });
+
+        log::debug!("Teams message: {json:?}");
+
+        log::debug!("Sending alert #{} to Teams", alert.id());
+        Ok(HttpRequest::post_json(url, json))
+    }
+}
+
+/// The number of bytes an element adds to the serialized card, including its separator
+fn element_size(element: &Value) -> usize {
+    serde_json::to_string(element).map_or(0, |s| s.len()) + 1
+}
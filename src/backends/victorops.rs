@@ -0,0 +1,118 @@
+use anyhow::Context;
+use reqwest::Url;
+use serde_json::json;
+
+use super::RetryPolicy;
+use crate::{
+    alert::{Action, AlertMeta, Severity},
+    http::HttpRequest,
+    utils::json_set_if_not_present,
+};
+
+/// The `VictorOps` struct implements a backend for [Splunk On-Call](https://www.splunk.com/en_us/products/on-call.html)
+/// (formerly VictorOps), using its REST endpoint integration.
+///
+/// Alert severities map to the `CRITICAL`, `WARNING` and `INFO` message types, dedup keys to the entity id and titles
+/// to the entity display name. Custom fields are sent as additional alert fields.
+///
+/// All alert [actions](crate::alert::Action) are supported. Acknowledging and resolving alerts refer to the incident
+/// triggered with the same dedup key, and are sent as `ACKNOWLEDGEMENT` and `RECOVERY` messages respectively.
+///
+/// The API key is the key component of the REST endpoint URL, and the routing key selects the team to alert:
+///
+/// ```
+/// use airbag::backends::VictorOps;
+///
+/// let backend = VictorOps::builder().api_key("some-api-key").routing_key("database").build();
+/// ```
+#[derive(typed_builder::TypedBuilder)]
+pub struct VictorOps {
+    #[builder(setter(into))]
+    api_key: String,
+
+    #[builder(setter(into))]
+    routing_key: String,
+
+    #[builder(default, setter(strip_option))]
+    base_url: Option<String>,
+
+    #[builder(default)]
+    retry_policy: RetryPolicy,
+}
+
+impl_http_backend!(VictorOps);
+
+impl VictorOps {
+    fn request(&self, alert: &crate::alert::Alert) -> anyhow::Result<HttpRequest> {
+        let mut url = Url::parse(
+            self.base_url
+                .as_deref()
+                .unwrap_or("https://alert.victorops.com"),
+        )
+        .context("Cannot parse URL")?;
+        url.path_segments_mut()
+            .map_err(|_| anyhow::format_err!("Cannot use base URL"))?
+            .clear()
+            .extend([
+                "integrations",
+                "generic",
+                "20131114",
+                "alert",
+                &self.api_key,
+                &self.routing_key,
+            ]);
+
+        let AlertMeta {
+            title,
+            dedup_key,
+            description,
+            action,
+            ..
+        } = alert.meta();
+
+        let mut json = match action {
+            Action::Trigger => {
+                let mut json = alert.as_json().clone();
+
+                let severity = alert.meta().effective_severity();
+                json["message_type"] = json!(match severity {
+                    Severity::Critical | Severity::Error => "CRITICAL",
+                    Severity::Warning => "WARNING",
+                    Severity::Info => "INFO",
+                });
+
+                json_set_if_not_present(
+                    &mut json,
+                    &["entity_display_name"],
+                    title.as_deref().unwrap_or("Airbag alert"),
+                );
+
+                if let Some(description) = description {
+                    json_set_if_not_present(&mut json, &["state_message"], description);
+                }
+
+                if let Some(dedup_key) = dedup_key {
+                    json["entity_id"] = json!(dedup_key);
+                }
+                json
+            }
+            Action::Acknowledge | Action::Resolve => json!({
+                "message_type": if *action == Action::Resolve {
+                    "RECOVERY"
+                } else {
+                    "ACKNOWLEDGEMENT"
+                },
+                "entity_id": dedup_key
+                    .as_deref()
+                    .context("VictorOps requires a dedup key to acknowledge or resolve incidents")
+                    .map_err(super::permanent)?,
+            }),
+        };
+        json_set_if_not_present(&mut json, &["monitoring_tool"], "airbag");
+
+        log::debug!("VictorOps event: {json:?}");
+
+        log::debug!("Sending alert #{} to VictorOps", alert.id());
+        Ok(HttpRequest::post_json(url, json))
+    }
+}
@@ -0,0 +1,183 @@
+use anyhow::Context;
+use reqwest::{Method, Url};
+use serde_json::{json, Value};
+
+use super::RetryPolicy;
+use crate::{alert::AlertMeta, http::HttpRequest};
+
+/// The `Webhook` struct implements a generic backend, sending alerts to an arbitrary HTTP endpoint.
+///
+/// By default, the request body is a JSON object holding the following values. A body template can be given instead,
+/// in which string values may contain `{{...}}` placeholders referring to them:
+///
+/// * `id`, `title`, `description`, `dedup_key`, `severity`, `priority` and `action`
+/// * `fields`, holding the alert's fields. Nested values are referred to by dotted paths, e.g. `{{fields.host}}`
+///
+/// A string consisting of a single placeholder is replaced with the referred value as is, preserving its JSON type.
+/// Otherwise, placeholders are interpolated into the string. Missing values render as `null`, or as an empty string
+/// when interpolated.
+///
+/// ```
+/// use airbag::backends::Webhook;
+/// use serde_json::json;
+///
+/// let backend = Webhook::builder()
+///     .url("https://incidents.example.com/api/events")
+///     .headers(vec![("X-Api-Key".to_owned(), "some-key".to_owned())])
+///     .body(json!({
+///         "summary": "[{{severity}}] {{title}}",
+///         "key": "{{dedup_key}}",
+///         "context": "{{fields}}",
+///     }))
+///     .build();
+/// ```
+#[derive(typed_builder::TypedBuilder)]
+pub struct Webhook {
+    #[builder(setter(into))]
+    url: String,
+
+    #[builder(default = "POST".into(), setter(into))]
+    method: String,
+
+    #[builder(default)]
+    headers: Vec<(String, String)>,
+
+    #[builder(default, setter(strip_option))]
+    body: Option<Value>,
+
+    #[builder(default)]
+    retry_policy: RetryPolicy,
+}
+
+impl_http_backend!(Webhook);
+
+impl Webhook {
+    fn request(&self, alert: &crate::alert::Alert) -> anyhow::Result<HttpRequest> {
+        let url = Url::parse(&self.url).context("Cannot parse URL")?;
+        let method = Method::from_bytes(self.method.to_uppercase().as_bytes())
+            .with_context(|| format!("Invalid HTTP method: {:?}", self.method))?;
+
+        let context = template_context(alert);
+        let body = match &self.body {
+            Some(template) => render(template, &context),
+            None => context,
+        };
+
+        log::debug!("Webhook body: {body:?}");
+
+        let mut request = HttpRequest::new(method, url);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+
+        log::debug!("Sending alert #{} to webhook", alert.id());
+        Ok(request.json(body))
+    }
+}
+
+fn template_context(alert: &crate::alert::Alert) -> Value {
+    let AlertMeta {
+        title,
+        description,
+        dedup_key,
+        severity,
+        priority,
+        action,
+        ..
+    } = alert.meta();
+
+    json!({
+        "id": alert.id(),
+        "title": title,
+        "description": description,
+        "dedup_key": dedup_key,
+        "severity": severity.map(|s| s.as_str()),
+        "priority": priority.map(|p| p.as_str()),
+        "action": action.as_str(),
+        "fields": alert.get_fields(),
+    })
+}
+
+fn render(template: &Value, context: &Value) -> Value {
+    match template {
+        Value::String(s) => render_string(s, context),
+        Value::Array(values) => values.iter().map(|v| render(v, context)).collect(),
+        Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| (k.clone(), render(v, context)))
+            .collect::<serde_json::Map<_, _>>()
+            .into(),
+        other => other.clone(),
+    }
+}
+
+fn render_string(s: &str, context: &Value) -> Value {
+    if let Some(path) = s
+        .strip_prefix("{{")
+        .and_then(|rest| rest.strip_suffix("}}"))
+        .filter(|path| !path.contains("{{") && !path.contains("}}"))
+    {
+        return lookup(context, path).cloned().unwrap_or(Value::Null);
+    }
+
+    let mut rendered = String::new();
+    let mut rest = s;
+    while let Some(start) = rest.find("{{") {
+        let Some(end) = rest[start..].find("}}") else {
+            break;
+        };
+        rendered.push_str(&rest[..start]);
+        match lookup(context, &rest[start + 2..start + end]) {
+            Some(Value::String(value)) => rendered.push_str(value),
+            Some(Value::Null) | None => {}
+            Some(value) => rendered.push_str(&value.to_string()),
+        }
+        rest = &rest[start + end + 2..];
+    }
+    rendered.push_str(rest);
+    Value::String(rendered)
+}
+
+fn lookup<'a>(context: &'a Value, path: &str) -> Option<&'a Value> {
+    path.trim()
+        .split('.')
+        .try_fold(context, |value, part| value.get(part))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::render;
+
+    #[test]
+    fn test_render() {
+        let context = json!({
+            "title": "Disk full",
+            "description": null,
+            "fields": {"host": "db-1", "usage": 97, "tags": ["a", "b"]},
+        });
+
+        assert_eq!(
+            render(
+                &json!({
+                    "summary": "{{title}} on {{ fields.host }} ({{fields.usage}}%)",
+                    "description": "{{description}}",
+                    "tags": "{{fields.tags}}",
+                    "missing": "[{{fields.missing}}]",
+                    "nested": [{"usage": "{{fields.usage}}"}, 42],
+                    "unterminated": "{{title",
+                }),
+                &context
+            ),
+            json!({
+                "summary": "Disk full on db-1 (97%)",
+                "description": null,
+                "tags": ["a", "b"],
+                "missing": "[]",
+                "nested": [{"usage": 97}, 42],
+                "unterminated": "{{title",
+            })
+        );
+    }
+}
@@ -0,0 +1,90 @@
+use std::{
+    sync::Arc,
+    task::{Poll, Waker},
+    time::Instant,
+};
+
+/// A value which becomes available at some point in the future, and can be waited on either by blocking or by
+/// awaiting it
+pub(crate) struct Completion<T> {
+    state: Arc<(parking_lot::Mutex<CompletionState<T>>, parking_lot::Condvar)>,
+}
+
+struct CompletionState<T> {
+    value: Option<T>,
+    wakers: Vec<Waker>,
+}
+
+impl<T> Default for Completion<T> {
+    fn default() -> Self {
+        Self {
+            state: Arc::new((
+                parking_lot::Mutex::new(CompletionState {
+                    value: None,
+                    wakers: Vec::new(),
+                }),
+                parking_lot::Condvar::new(),
+            )),
+        }
+    }
+}
+
+impl<T> Clone for Completion<T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<T: Clone> Completion<T> {
+    pub(crate) fn complete(&self, value: T) {
+        let mut state = self.state.0.lock();
+        state.value.replace(value);
+        for waker in state.wakers.drain(..) {
+            waker.wake();
+        }
+        self.state.1.notify_all();
+    }
+
+    pub(crate) fn wait(&self) -> T {
+        let mut state = self.state.0.lock();
+        loop {
+            if let Some(value) = &state.value {
+                break value.clone();
+            }
+            self.state.1.wait(&mut state);
+        }
+    }
+
+    /// Waits until the value is available or until the deadline has passed, if one is given
+    pub(crate) fn wait_deadline(&self, deadline: Option<Instant>) -> Option<T> {
+        let Some(deadline) = deadline else {
+            return Some(self.wait());
+        };
+        let mut state = self.state.0.lock();
+        loop {
+            if let Some(value) = &state.value {
+                break Some(value.clone());
+            }
+            if self.state.1.wait_until(&mut state, deadline).timed_out() {
+                break state.value.clone();
+            }
+        }
+    }
+}
+
+impl<T: Clone> std::future::Future for Completion<T> {
+    type Output = T;
+
+    fn poll(self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<T> {
+        let mut state = self.state.0.lock();
+        if let Some(value) = &state.value {
+            return Poll::Ready(value.clone());
+        }
+        if !state.wakers.iter().any(|w| w.will_wake(cx.waker())) {
+            state.wakers.push(cx.waker().clone());
+        }
+        Poll::Pending
+    }
+}
@@ -0,0 +1,364 @@
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+use parking_lot::Mutex;
+
+use crate::alert::{Action, Alert};
+
+pub(crate) const DEFAULT_DEDUP_WINDOW: Duration = Duration::from_secs(5);
+
+/// Keeps track of recently sent dedup keys, suppressing repeated triggers within the configured window.
+///
+/// Suppressed alerts are counted, and once the window of a key expires a summary alert is emitted for it, carrying
+/// the number of occurrences and when they were first and last seen
+pub(crate) struct Deduplicator {
+    window: Arc<Mutex<Duration>>,
+    recent: HashMap<String, Sent>,
+}
+
+struct Sent {
+    at: Instant,
+    window: Duration,
+    first_seen: SystemTime,
+    suppressed: Option<Suppressed>,
+}
+
+struct Suppressed {
+    count: usize,
+    last_seen: SystemTime,
+    last_alert: Alert,
+}
+
+impl Sent {
+    fn expired(&self, now: Instant) -> bool {
+        now.duration_since(self.at) >= self.window
+    }
+}
+
+/// The dedup-relevant details of an alert, retained while the alert itself is handed over to the backend
+pub(crate) struct DedupEntry {
+    key: Option<String>,
+    action: Action,
+    window: Duration,
+}
+
+impl Deduplicator {
+    pub(crate) fn new(window: Arc<Mutex<Duration>>) -> Self {
+        Self {
+            window,
+            recent: Default::default(),
+        }
+    }
+
+    pub(crate) fn entry(&self, alert: &Alert) -> DedupEntry {
+        DedupEntry {
+            key: alert.dedup_key().clone(),
+            action: alert.action(),
+            window: alert
+                .meta()
+                .dedup_window
+                .unwrap_or_else(|| *self.window.lock()),
+        }
+    }
+
+    /// Returns whether the alert is a duplicate of a recently sent one. Only repeated triggers are considered
+    /// duplicates -- acknowledging or resolving an incident should always go through
+    pub(crate) fn is_duplicate(&self, entry: &DedupEntry, now: Instant) -> bool {
+        match (&entry.key, entry.action) {
+            (Some(key), Action::Trigger) => self
+                .recent
+                .get(key)
+                .is_some_and(|sent| now.duration_since(sent.at) < entry.window),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn mark_sent(&mut self, entry: &DedupEntry, now: Instant) {
+        let Some(key) = entry.key.clone() else {
+            return;
+        };
+        match entry.action {
+            Action::Trigger => {
+                // later alerts may fall back to the hub's window, so the key is retained for at least as long
+                let window = entry.window.max(*self.window.lock());
+                self.recent.insert(
+                    key,
+                    Sent {
+                        at: now,
+                        window,
+                        first_seen: SystemTime::now(),
+                        suppressed: None,
+                    },
+                );
+            }
+            Action::Resolve => {
+                // allow the incident to be re-triggered right away. Occurrences suppressed until now are no longer
+                // relevant once the incident is resolved
+                self.recent.remove(&key);
+            }
+            Action::Acknowledge => {}
+        }
+    }
+
+    /// Forgets an alert previously marked as sent, e.g. when sending it eventually failed
+    #[cfg(feature = "tokio")]
+    pub(crate) fn forget(&mut self, entry: &DedupEntry) {
+        if let (Some(key), Action::Trigger) = (&entry.key, entry.action) {
+            self.recent.remove(key);
+        }
+    }
+
+    /// Counts an alert which was suppressed as a duplicate
+    pub(crate) fn mark_suppressed(&mut self, entry: &DedupEntry, alert: Alert) {
+        let Some(sent) = entry.key.as_ref().and_then(|key| self.recent.get_mut(key)) else {
+            return;
+        };
+        let count = sent.suppressed.as_ref().map_or(0, |s| s.count) + 1;
+        sent.suppressed.replace(Suppressed {
+            count,
+            last_seen: SystemTime::now(),
+            last_alert: alert,
+        });
+    }
+
+    /// Returns the earliest time at which a window with suppressed occurrences expires, if any
+    pub(crate) fn next_summary_at(&self) -> Option<Instant> {
+        self.recent
+            .values()
+            .filter(|sent| sent.suppressed.is_some())
+            .map(|sent| sent.at + sent.window)
+            .min()
+    }
+
+    /// Forgets keys whose window expired, returning summary alerts for the ones which had suppressed occurrences.
+    /// When `flush` is set, summaries are returned for all keys regardless of their window
+    pub(crate) fn take_summaries(&mut self, now: Instant, flush: bool) -> Vec<Alert> {
+        let mut summaries = Vec::new();
+        self.recent.retain(|_, sent| {
+            if !flush && !sent.expired(now) {
+                return true;
+            }
+            if let Some(suppressed) = sent.suppressed.take() {
+                summaries.push(summary_alert(sent.first_seen, suppressed));
+            }
+            false
+        });
+        summaries
+    }
+}
+
+fn summary_alert(first_seen: SystemTime, suppressed: Suppressed) -> Alert {
+    // the first occurrence was sent, and the rest were suppressed
+    let occurrences = suppressed.count + 1;
+    let mut alert = suppressed
+        .last_alert
+        .with_field("occurrences", occurrences)
+        .with_field(
+            "first_seen",
+            humantime::format_rfc3339_seconds(first_seen).to_string(),
+        )
+        .with_field(
+            "last_seen",
+            humantime::format_rfc3339_seconds(suppressed.last_seen).to_string(),
+        );
+    let title = alert.meta().title.as_deref().unwrap_or("Airbag alert");
+    let title = format!("{title} (repeated {occurrences} times)");
+    alert.meta_mut().title.replace(title);
+    alert
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+
+    use super::Deduplicator;
+    use crate::alert::{Action, Alert};
+
+    fn deduplicator(window: Duration) -> Deduplicator {
+        Deduplicator::new(Arc::new(parking_lot::Mutex::new(window)))
+    }
+
+    /// Sends the alert through the deduplicator, returning whether it was sent
+    fn send(dedup: &mut Deduplicator, alert: Alert, now: Instant) -> bool {
+        let entry = dedup.entry(&alert);
+        if dedup.is_duplicate(&entry, now) {
+            dedup.mark_suppressed(&entry, alert);
+            false
+        } else {
+            dedup.mark_sent(&entry, now);
+            true
+        }
+    }
+
+    #[test]
+    fn test_dedup_window() {
+        let mut dedup = deduplicator(Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(send(
+            &mut dedup,
+            Alert::builder().dedup_key("a").build(),
+            now
+        ));
+        assert!(!send(
+            &mut dedup,
+            Alert::builder().dedup_key("a").build(),
+            now + Duration::from_secs(30)
+        ));
+        assert!(send(
+            &mut dedup,
+            Alert::builder().dedup_key("b").build(),
+            now
+        ));
+        assert!(send(&mut dedup, Alert::builder().build(), now));
+        assert!(send(
+            &mut dedup,
+            Alert::builder().dedup_key("a").build(),
+            now + Duration::from_secs(61)
+        ));
+    }
+
+    #[test]
+    fn test_dedup_window_per_alert() {
+        let mut dedup = deduplicator(Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(send(
+            &mut dedup,
+            Alert::builder().dedup_key("a").build(),
+            now
+        ));
+        assert!(send(
+            &mut dedup,
+            Alert::builder()
+                .dedup_key("a")
+                .dedup_window(Duration::from_secs(10))
+                .build(),
+            now + Duration::from_secs(30)
+        ));
+        assert!(send(
+            &mut dedup,
+            Alert::builder()
+                .dedup_key("a")
+                .dedup_window(Duration::ZERO)
+                .build(),
+            now + Duration::from_secs(31)
+        ));
+    }
+
+    #[test]
+    fn test_dedup_disabled() {
+        let mut dedup = deduplicator(Duration::ZERO);
+        let now = Instant::now();
+
+        assert!(send(
+            &mut dedup,
+            Alert::builder().dedup_key("a").build(),
+            now
+        ));
+        assert!(send(
+            &mut dedup,
+            Alert::builder().dedup_key("a").build(),
+            now
+        ));
+    }
+
+    #[test]
+    fn test_resolve_is_never_suppressed() {
+        let mut dedup = deduplicator(Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(send(
+            &mut dedup,
+            Alert::builder().dedup_key("a").build(),
+            now
+        ));
+        assert!(send(
+            &mut dedup,
+            Alert::builder()
+                .dedup_key("a")
+                .action(Action::Resolve)
+                .build(),
+            now
+        ));
+        assert!(send(
+            &mut dedup,
+            Alert::builder().dedup_key("a").build(),
+            now
+        ));
+    }
+
+    #[test]
+    fn test_occurrence_summary() {
+        let mut dedup = deduplicator(Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(send(
+            &mut dedup,
+            Alert::builder().title("hello").dedup_key("a").build(),
+            now
+        ));
+        assert!(dedup.next_summary_at().is_none());
+
+        for i in 1..=3 {
+            assert!(!send(
+                &mut dedup,
+                Alert::builder()
+                    .title("hello")
+                    .dedup_key("a")
+                    .build()
+                    .with_field("i", i),
+                now + Duration::from_secs(i)
+            ));
+        }
+        assert_eq!(dedup.next_summary_at(), Some(now + Duration::from_secs(60)));
+
+        assert!(dedup
+            .take_summaries(now + Duration::from_secs(30), false)
+            .is_empty());
+
+        let summaries = dedup.take_summaries(now + Duration::from_secs(60), false);
+        assert_eq!(summaries.len(), 1);
+        let summary = &summaries[0];
+        assert_eq!(summary.title(), &Some("hello (repeated 4 times)".into()));
+        assert_eq!(summary.dedup_key(), &Some("a".into()));
+        assert_eq!(summary.get_field("occurrences"), Some(&4.into()));
+        assert_eq!(summary.get_field("i"), Some(&3.into()));
+        assert!(summary.get_field("first_seen").is_some());
+        assert!(summary.get_field("last_seen").is_some());
+
+        assert!(dedup.next_summary_at().is_none());
+    }
+
+    #[test]
+    fn test_occurrence_summary_flush() {
+        let mut dedup = deduplicator(Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(send(
+            &mut dedup,
+            Alert::builder().dedup_key("a").build(),
+            now
+        ));
+        assert!(send(
+            &mut dedup,
+            Alert::builder().dedup_key("b").build(),
+            now
+        ));
+        assert!(!send(
+            &mut dedup,
+            Alert::builder().dedup_key("a").build(),
+            now
+        ));
+
+        let summaries = dedup.take_summaries(now, true);
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].dedup_key(), &Some("a".into()));
+    }
+}
@@ -0,0 +1,240 @@
+use std::ops::ControlFlow;
+
+use anyhow::Context;
+use reqwest::{header::HeaderMap, Method, StatusCode, Url};
+
+use crate::backends::RetryPolicy;
+
+/// An HTTP request emitted by a backend, which can be sent either in a blocking manner or asynchronously, retrying
+/// according to a [RetryPolicy]
+pub(crate) struct HttpRequest {
+    method: Method,
+    url: Url,
+    headers: Vec<(String, String)>,
+    body: HttpBody,
+}
+
+enum HttpBody {
+    Empty,
+    Json(serde_json::Value),
+    Text(String),
+    Form(Vec<(String, String)>),
+}
+
+enum RequestError {
+    ClientSideError(anyhow::Error),
+    ServerSideError(anyhow::Error),
+    RateLimited(anyhow::Error, Option<std::time::Duration>),
+}
+
+impl HttpRequest {
+    pub(crate) fn new(method: Method, url: Url) -> Self {
+        Self {
+            method,
+            url,
+            headers: Vec::new(),
+            body: HttpBody::Empty,
+        }
+    }
+
+    pub(crate) fn post_json(url: Url, json: serde_json::Value) -> Self {
+        Self::new(Method::POST, url).json(json)
+    }
+
+    pub(crate) fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    pub(crate) fn json(mut self, json: serde_json::Value) -> Self {
+        self.body = HttpBody::Json(json);
+        self
+    }
+
+    pub(crate) fn text(mut self, text: impl Into<String>) -> Self {
+        self.body = HttpBody::Text(text.into());
+        self
+    }
+
+    pub(crate) fn form(mut self, form: Vec<(String, String)>) -> Self {
+        self.body = HttpBody::Form(form);
+        self
+    }
+
+    pub(crate) fn send(&self, policy: &RetryPolicy) -> anyhow::Result<()> {
+        let client = reqwest::blocking::Client::new();
+
+        let start = std::time::Instant::now();
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+            let res = self
+                .blocking_request(&client)
+                .send()
+                .context("Failed sending HTTP request")
+                .map_err(RequestError::ServerSideError)
+                .and_then(|resp| {
+                    let status = resp.status();
+                    let headers = resp.headers().clone();
+                    check_response(status, &headers, || resp.text().ok())
+                });
+
+            match retry_delay(policy, res, attempts, start) {
+                ControlFlow::Continue(delay) => std::thread::sleep(delay),
+                ControlFlow::Break(res) => break res,
+            }
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    pub(crate) async fn send_async(&self, policy: &RetryPolicy) -> anyhow::Result<()> {
+        static CLIENT: std::sync::OnceLock<reqwest::Client> = std::sync::OnceLock::new();
+        let client = CLIENT.get_or_init(reqwest::Client::new);
+
+        let start = std::time::Instant::now();
+        let mut attempts = 0;
+
+        loop {
+            attempts += 1;
+            let res = match self.async_request(client).send().await {
+                Ok(resp) => {
+                    let status = resp.status();
+                    let headers = resp.headers().clone();
+                    let body = if status.is_success() {
+                        None
+                    } else {
+                        resp.text().await.ok()
+                    };
+                    check_response(status, &headers, || body)
+                }
+                Err(e) => Err(RequestError::ServerSideError(
+                    anyhow::Error::new(e).context("Failed sending HTTP request"),
+                )),
+            };
+
+            match retry_delay(policy, res, attempts, start) {
+                ControlFlow::Continue(delay) => tokio::time::sleep(delay).await,
+                ControlFlow::Break(res) => break res,
+            }
+        }
+    }
+
+    fn blocking_request(
+        &self,
+        client: &reqwest::blocking::Client,
+    ) -> reqwest::blocking::RequestBuilder {
+        let mut builder = client.request(self.method.clone(), self.url.clone());
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        match &self.body {
+            HttpBody::Empty => builder,
+            HttpBody::Json(json) => builder.json(json),
+            HttpBody::Text(text) => builder
+                .header(reqwest::header::CONTENT_TYPE, "text/plain; charset=utf-8")
+                .body(text.clone()),
+            HttpBody::Form(form) => builder.form(form),
+        }
+    }
+
+    #[cfg(feature = "tokio")]
+    fn async_request(&self, client: &reqwest::Client) -> reqwest::RequestBuilder {
+        let mut builder = client.request(self.method.clone(), self.url.clone());
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        match &self.body {
+            HttpBody::Empty => builder,
+            HttpBody::Json(json) => builder.json(json),
+            HttpBody::Text(text) => builder
+                .header(reqwest::header::CONTENT_TYPE, "text/plain; charset=utf-8")
+                .body(text.clone()),
+            HttpBody::Form(form) => builder.form(form),
+        }
+    }
+}
+
+fn check_response<F: FnOnce() -> Option<String>>(
+    status: StatusCode,
+    headers: &HeaderMap,
+    body: F,
+) -> Result<(), RequestError> {
+    if status.is_success() {
+        return Ok(());
+    }
+    let body = body().map(|s| format!(": {s:?}")).unwrap_or_default();
+    let e = anyhow::format_err!("HTTP Error: {status}{body}");
+    Err(if status == StatusCode::TOO_MANY_REQUESTS {
+        RequestError::RateLimited(e, parse_retry_after(headers))
+    } else if status.is_client_error() {
+        RequestError::ClientSideError(e)
+    } else {
+        RequestError::ServerSideError(e)
+    })
+}
+
+/// Decides whether to retry a request given the result of its last attempt, returning the delay before the next
+/// attempt or the final result of the request
+fn retry_delay(
+    policy: &RetryPolicy,
+    res: Result<(), RequestError>,
+    attempts: u32,
+    start: std::time::Instant,
+) -> ControlFlow<anyhow::Result<()>, std::time::Duration> {
+    let (e, retry_after) = match res {
+        Ok(()) => return ControlFlow::Break(Ok(())),
+        Err(RequestError::ClientSideError(e)) => {
+            return ControlFlow::Break(Err(crate::backends::permanent(e)))
+        }
+        Err(RequestError::ServerSideError(e)) => (e, None),
+        Err(RequestError::RateLimited(e, retry_after)) => (e, retry_after),
+    };
+
+    match policy.next_delay(attempts, start.elapsed(), retry_after) {
+        Some(delay) => {
+            log::error!("Error while sending HTTP request: {e:?}. Retrying in {delay:?}...");
+            ControlFlow::Continue(delay)
+        }
+        None => ControlFlow::Break(Err(
+            e.context(format!("Giving up HTTP request after {attempts} attempts"))
+        )),
+    }
+}
+
+/// Parses a `Retry-After` header, given either in seconds or as an HTTP date
+fn parse_retry_after(headers: &HeaderMap) -> Option<std::time::Duration> {
+    let value = headers.get(reqwest::header::RETRY_AFTER)?.to_str().ok()?;
+    if let Ok(seconds) = value.trim().parse::<u64>() {
+        return Some(std::time::Duration::from_secs(seconds));
+    }
+    let date = httpdate::parse_http_date(value).ok()?;
+    Some(
+        date.duration_since(std::time::SystemTime::now())
+            .unwrap_or_default(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn test_parse_retry_after() {
+        use super::parse_retry_after;
+        use reqwest::header::{HeaderMap, HeaderValue, RETRY_AFTER};
+
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(
+            parse_retry_after(&headers),
+            Some(std::time::Duration::from_secs(120))
+        );
+
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(parse_retry_after(&headers), Some(std::time::Duration::ZERO));
+    }
+}
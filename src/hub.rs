@@ -1,12 +1,22 @@
 use std::{
     cell::RefCell,
-    collections::HashMap,
-    sync::{Arc, OnceLock},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, OnceLock,
+    },
 };
 
 use parking_lot::Mutex;
 
-use crate::{backends::Backend, middleware::Middleware};
+use crate::{
+    backends::{spool::SpoolHandle, Backend},
+    completion::Completion,
+    dedup::{Deduplicator, DEFAULT_DEDUP_WINDOW},
+    middleware::Middleware,
+};
+
+#[cfg(feature = "tokio")]
+mod async_dispatch;
 
 static GLOBAL_HUB: OnceLock<Hub> = OnceLock::new();
 
@@ -14,19 +24,49 @@ thread_local! {
     static TL_HUB: RefCell<Option<Hub>> = const { RefCell::new(None) };
 }
 
-pub(crate) fn trigger(mut alert: crate::alert::Alert) -> ProcessingReceipt {
+pub(crate) fn trigger(alert: crate::alert::Alert) -> ProcessingReceipt {
+    trigger_on(get_backend(), alert)
+}
+
+/// Triggers an alert on a specific dispatch, rather than on the one currently configured for the calling thread
+pub(crate) fn trigger_on(
+    dispatch: Option<HubDispatch>,
+    mut alert: crate::alert::Alert,
+) -> ProcessingReceipt {
     let receipt = ProcessingReceipt::default();
-    if let Some(dispatch) = get_backend() {
+    if let Some(dispatch) = dispatch {
         log::debug!("Triggering alert #{}", alert.id());
         let middlewares = dispatch.middleware.lock().clone();
         for callback in middlewares {
-            alert = callback.process(alert);
+            match callback.filter(alert) {
+                Some(processed) => alert = processed,
+                None => {
+                    log::debug!("Alert dropped by middleware");
+                    receipt.mark_processed(DeliveryOutcome::Filtered);
+                    return receipt;
+                }
+            }
+        }
+        // persist the alert before queueing it, so that it survives a crash while queued
+        let spooled = dispatch
+            .spool
+            .as_ref()
+            .and_then(|spool| spool.write(&alert));
+        dispatch.pending.fetch_add(1, Ordering::SeqCst);
+        if !dispatch.sender.send(
+            HubMessage::Alert(alert, receipt.clone(), spooled.clone()),
+            None,
+        ) {
+            log::debug!("Hub is no longer running");
+            if let (Some(spool), Some(path)) = (&dispatch.spool, spooled) {
+                spool.remove(&path);
+            }
+            dispatch.pending.fetch_sub(1, Ordering::SeqCst);
+            receipt.mark_processed(DeliveryOutcome::NoHub);
         }
-        let _ = dispatch
-            .sender
-            .send(HubMessage::Alert(alert, receipt.clone()));
     } else {
         log::debug!("No hub is configured");
+        receipt.mark_processed(DeliveryOutcome::NoHub);
     }
     receipt
 }
@@ -37,14 +77,53 @@ pub(crate) struct Hub {
 }
 
 pub(crate) enum HubMessage {
-    Alert(crate::Alert, ProcessingReceipt),
-    Terminate(ProcessingReceipt),
+    /// An alert to deliver, along with the path of its spool file, if spooled
+    Alert(crate::Alert, ProcessingReceipt, Option<std::path::PathBuf>),
+    Flush(Completion<()>),
+    Terminate(Completion<()>),
+}
+
+/// The sending end of the channel to a hub's dispatcher, which runs either on a dedicated thread or as a task
+#[derive(Clone)]
+pub(crate) enum HubSender {
+    Thread(crossbeam::channel::Sender<HubMessage>),
+    #[cfg(feature = "tokio")]
+    Task(tokio::sync::mpsc::UnboundedSender<HubMessage>),
+}
+
+impl HubSender {
+    /// Sends a message to the dispatcher, blocking until the deadline (if given) when the channel is full. Returns
+    /// whether the message was sent, which fails if the dispatcher is no longer running or the deadline has passed
+    fn send(&self, msg: HubMessage, deadline: Option<std::time::Instant>) -> bool {
+        match self {
+            Self::Thread(sender) => match deadline {
+                Some(deadline) => sender.send_deadline(msg, deadline).is_ok(),
+                None => sender.send(msg).is_ok(),
+            },
+            #[cfg(feature = "tokio")]
+            Self::Task(sender) => sender.send(msg).is_ok(),
+        }
+    }
+
+    /// Blocks until the dispatcher completes the given signal, or until the deadline has passed. Returns whether the
+    /// signal was completed
+    fn wait(&self, done: &Completion<()>, deadline: Option<std::time::Instant>) -> bool {
+        match self {
+            Self::Thread(_) => done.wait_deadline(deadline).is_some(),
+            #[cfg(feature = "tokio")]
+            Self::Task(_) => async_dispatch::block_on(done, deadline),
+        }
+    }
 }
 
 #[derive(Clone)]
 pub(crate) struct HubDispatch {
-    sender: crossbeam::channel::Sender<HubMessage>,
+    sender: HubSender,
     middleware: Arc<Mutex<Vec<Arc<dyn Middleware + Send + Sync + 'static>>>>,
+    dedup_window: Arc<Mutex<std::time::Duration>>,
+    /// The number of alerts sent to the backend thread which were not processed yet
+    pending: Arc<AtomicUsize>,
+    spool: Option<SpoolHandle>,
 }
 
 pub(crate) fn get_backend() -> Option<HubDispatch> {
@@ -64,15 +143,41 @@ pub(crate) fn get_backend() -> Option<HubDispatch> {
 }
 
 pub fn configure<B: Backend + Send + 'static>(backend: B) -> ConfiguredHubGuard {
-    let dispatch = spawn_backend(backend);
+    install_global(spawn_backend(backend))
+}
+
+pub fn configure_thread_local<B: Backend + Send + 'static>(backend: B) -> ConfiguredHubGuard {
+    install_thread_local(spawn_backend(backend))
+}
+
+/// Configures a global hub delivering alerts through an [AsyncBackend](crate::backends::AsyncBackend).
+///
+/// Instead of a dedicated thread, alerts are delivered by tasks spawned on the current tokio runtime, allowing
+/// multiple alerts to be in flight concurrently. Must be called from within a tokio runtime.
+///
+/// Dropping the returned guard blocks until pending alerts are delivered, which is not possible on a current-thread
+/// runtime -- use [ConfiguredHubGuard::shutdown] to flush alerts from async code instead
+#[cfg(feature = "tokio")]
+pub fn configure_async<B: crate::backends::AsyncBackend>(backend: B) -> ConfiguredHubGuard {
+    install_global(async_dispatch::spawn_backend(backend))
+}
+
+/// The thread-local counterpart of [configure_async]. Must be called from within a tokio runtime
+#[cfg(feature = "tokio")]
+pub fn configure_thread_local_async<B: crate::backends::AsyncBackend>(
+    backend: B,
+) -> ConfiguredHubGuard {
+    install_thread_local(async_dispatch::spawn_backend(backend))
+}
+
+fn install_global(dispatch: HubDispatch) -> ConfiguredHubGuard {
     let global_hub = GLOBAL_HUB.get_or_init(Default::default);
     global_hub.dispatch.lock().replace(dispatch.clone());
     crate::panic_handler::install();
-    ConfiguredHubGuard { dispatch }
+    ConfiguredHubGuard::new(dispatch)
 }
 
-pub fn configure_thread_local<B: Backend + Send + 'static>(backend: B) -> ConfiguredHubGuard {
-    let dispatch = spawn_backend(backend);
+fn install_thread_local(dispatch: HubDispatch) -> ConfiguredHubGuard {
     TL_HUB.with(|hub| {
         let mut hub = hub.borrow_mut();
         assert!(
@@ -84,53 +189,84 @@ pub fn configure_thread_local<B: Backend + Send + 'static>(backend: B) -> Config
         });
     });
 
-    ConfiguredHubGuard { dispatch }
+    ConfiguredHubGuard::new(dispatch)
 }
 
-const MIN_INTERVAL_BETWEEN_DUP_ALERTS: std::time::Duration = std::time::Duration::from_secs(5);
+impl HubDispatch {
+    fn new(sender: HubSender) -> Self {
+        Self {
+            sender,
+            middleware: Default::default(),
+            dedup_window: Arc::new(Mutex::new(DEFAULT_DEDUP_WINDOW)),
+            pending: Default::default(),
+            spool: None,
+        }
+    }
+}
 
 fn spawn_backend<B: Backend + Send + 'static>(mut backend: B) -> HubDispatch {
     let (sender, receiver) = crossbeam::channel::bounded(1024);
+    let mut dispatch = HubDispatch::new(HubSender::Thread(sender));
+    dispatch.spool = backend.open_spool();
+    let spool = dispatch.spool.clone();
+    let mut dedup = Deduplicator::new(dispatch.dedup_window.clone());
+    let pending = dispatch.pending.clone();
     std::thread::spawn(move || {
-        let mut recent_dedup_keys: HashMap<String, std::time::Instant> = HashMap::new();
         log::debug!("Backend started...");
-        while let Ok(msg) = receiver.recv() {
+        backend.on_start();
+        if let Some(spool) = &spool {
+            if let Err(e) = spool.replay(&mut backend) {
+                log::error!("Failed replaying spooled alerts: {e:?}");
+            }
+        }
+        loop {
+            // wake up when a dedup window expires, so that summaries of suppressed alerts are sent in time
+            let msg = match dedup.next_summary_at() {
+                Some(deadline) => match receiver.recv_deadline(deadline) {
+                    Ok(msg) => Some(msg),
+                    Err(crossbeam::channel::RecvTimeoutError::Timeout) => None,
+                    Err(crossbeam::channel::RecvTimeoutError::Disconnected) => break,
+                },
+                None => match receiver.recv() {
+                    Ok(msg) => Some(msg),
+                    Err(_) => break,
+                },
+            };
+
+            for summary in dedup.take_summaries(std::time::Instant::now(), false) {
+                deliver(&mut backend, &mut dedup, summary);
+            }
+
             match msg {
-                HubMessage::Alert(alert, receipt) => {
-                    let alert_id = alert.id();
-                    let now = std::time::Instant::now();
-
-                    let should_send = if let Some(dedup_key) = &alert.meta().dedup_key {
-                        recent_dedup_keys.retain(|_, last_sent| {
-                            *last_sent >= now - MIN_INTERVAL_BETWEEN_DUP_ALERTS
-                        });
-                        !recent_dedup_keys.contains_key(dedup_key)
-                    } else {
-                        true
-                    };
-
-                    if should_send {
-                        let dedup_key = alert.meta().dedup_key.clone();
-                        log::debug!("Backend got alert #{alert_id}. Sending...");
-                        let res = backend.send(alert);
-                        if let Err(e) = res {
-                            log::error!("Failed sending alert: {e:?}");
-                        } else {
-                            log::debug!("Alert #{alert_id} sent successfully");
-                            if let Some(key) = dedup_key {
-                                recent_dedup_keys.insert(key, now);
-                            }
+                Some(HubMessage::Alert(alert, receipt, spooled)) => {
+                    let outcome = deliver(&mut backend, &mut dedup, alert);
+                    // alerts which failed are kept, to be replayed on the next start, unless no retry can fix them
+                    if let (Some(spool), Some(path)) = (&spool, spooled) {
+                        match &outcome {
+                            DeliveryOutcome::Failed(e) if !crate::backends::is_permanent(e) => {}
+                            _ => spool.remove(&path),
                         }
-                    } else {
-                        log::debug!("Skipping sending #{alert_id} - same dedup key sent recently");
                     }
-                    receipt.mark_processed();
+                    receipt.mark_processed(outcome);
+                    pending.fetch_sub(1, Ordering::SeqCst);
+                }
+                Some(HubMessage::Flush(done)) => {
+                    done.complete(());
                 }
-                HubMessage::Terminate(receipt) => {
+                Some(HubMessage::Terminate(done)) => {
                     log::debug!("Backend received termination signal");
-                    receipt.mark_processed();
+                    for summary in dedup.take_summaries(std::time::Instant::now(), true) {
+                        deliver(&mut backend, &mut dedup, summary);
+                    }
+                    // release the spool's lock before reporting termination, so that its leftovers can be
+                    // replayed, even though the dispatch of a global hub still holds the spool
+                    if let Some(spool) = &spool {
+                        spool.release();
+                    }
+                    done.complete(());
                     break;
                 }
+                None => continue,
             }
             log::debug!("Backend waiting for next message...")
         }
@@ -138,17 +274,50 @@ fn spawn_backend<B: Backend + Send + 'static>(mut backend: B) -> HubDispatch {
         log::debug!("Backend thread terminating")
     });
 
-    HubDispatch {
-        sender,
-        middleware: Default::default(),
+    dispatch
+}
+
+fn deliver<B: Backend>(
+    backend: &mut B,
+    dedup: &mut Deduplicator,
+    alert: crate::Alert,
+) -> DeliveryOutcome {
+    let alert_id = alert.id();
+    let now = std::time::Instant::now();
+    let dedup_entry = dedup.entry(&alert);
+
+    if dedup.is_duplicate(&dedup_entry, now) {
+        log::debug!("Skipping sending #{alert_id} - same dedup key sent recently");
+        dedup.mark_suppressed(&dedup_entry, alert);
+        return DeliveryOutcome::Suppressed;
+    }
+
+    log::debug!("Backend got alert #{alert_id}. Sending...");
+    if let Err(e) = backend.send(alert) {
+        log::error!("Failed sending alert: {e:?}");
+        DeliveryOutcome::Failed(Arc::new(e))
+    } else {
+        log::debug!("Alert #{alert_id} sent successfully");
+        dedup.mark_sent(&dedup_entry, now);
+        DeliveryOutcome::Sent
     }
 }
 
 pub struct ConfiguredHubGuard {
     dispatch: HubDispatch,
+    shutdown_timeout: Option<std::time::Duration>,
+    terminated: bool,
 }
 
 impl ConfiguredHubGuard {
+    fn new(dispatch: HubDispatch) -> Self {
+        Self {
+            dispatch,
+            shutdown_timeout: None,
+            terminated: false,
+        }
+    }
+
     /// installs a middleware to the configured hub
     pub fn with_middleware<M: Middleware + Send + Sync + 'static>(self, middleware: M) -> Self {
         self.dispatch.middleware.lock().push(Arc::new(middleware));
@@ -162,38 +331,163 @@ impl ConfiguredHubGuard {
     {
         self.with_middleware(crate::middleware::Map::new(f))
     }
+
+    /// Installs a middleware that only lets alerts matching the given predicate through
+    pub fn filter<F: Fn(&crate::Alert) -> bool + Send + Sync + 'static>(self, f: F) -> Self {
+        self.with_middleware(crate::middleware::Filter::new(f))
+    }
+
+    /// Sets the window in which repeated alerts with the same dedup key are suppressed locally, instead of being
+    /// sent to the backend. Defaults to 5 seconds, and can be overridden per alert using
+    /// [AlertBuilder::dedup_window](crate::alert::AlertBuilder::dedup_window)
+    pub fn with_dedup_window(self, window: std::time::Duration) -> Self {
+        *self.dispatch.dedup_window.lock() = window;
+        self
+    }
+
+    /// Disables local suppression of repeated alerts, leaving deduplication entirely to the backend service
+    pub fn without_dedup_suppression(self) -> Self {
+        self.with_dedup_window(std::time::Duration::ZERO)
+    }
+
+    /// Limits the time spent flushing pending alerts when the guard is dropped. By default, dropping the guard waits
+    /// until all pending alerts are processed, which can take arbitrarily long if the backend keeps retrying
+    pub fn with_shutdown_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.shutdown_timeout.replace(timeout);
+        self
+    }
+
+    /// Waits up to `timeout` for the alerts triggered so far to be processed, returning the number of alerts still
+    /// pending
+    pub fn flush(&self, timeout: std::time::Duration) -> usize {
+        let deadline = std::time::Instant::now() + timeout;
+        let done = Completion::default();
+
+        if self
+            .dispatch
+            .sender
+            .send(HubMessage::Flush(done.clone()), Some(deadline))
+        {
+            self.dispatch.sender.wait(&done, Some(deadline));
+        }
+        self.dispatch.pending.load(Ordering::SeqCst)
+    }
+
+    /// Shuts the hub down, waiting for pending alerts to be processed without blocking the executor. Like dropping
+    /// the guard, waiting is bounded by the shutdown timeout if one is set
+    #[cfg(feature = "tokio")]
+    pub async fn shutdown(mut self) {
+        self.terminated = true;
+        let done = Completion::default();
+
+        log::debug!("Flushing airbag alerts...");
+        if !self
+            .dispatch
+            .sender
+            .send(HubMessage::Terminate(done.clone()), None)
+        {
+            return;
+        }
+        match self.shutdown_timeout {
+            Some(timeout) => {
+                if tokio::time::timeout(timeout, done).await.is_err() {
+                    self.warn_pending();
+                }
+            }
+            None => done.await,
+        }
+    }
+
+    fn warn_pending(&self) {
+        log::warn!(
+            "Timed out flushing airbag alerts ({} still pending)",
+            self.dispatch.pending.load(Ordering::SeqCst)
+        );
+    }
 }
 
 impl Drop for ConfiguredHubGuard {
     fn drop(&mut self) {
-        let receipt = ProcessingReceipt::default();
+        if self.terminated {
+            return;
+        }
+        let deadline = self
+            .shutdown_timeout
+            .map(|timeout| std::time::Instant::now() + timeout);
+        let done = Completion::default();
 
-        let _ = self
+        log::debug!("Flushing airbag alerts...");
+        let flushed = if self
             .dispatch
             .sender
-            .send(HubMessage::Terminate(receipt.clone()));
-
-        log::debug!("Flushing airbag alerts...");
-        receipt.wait_processed();
+            .send(HubMessage::Terminate(done.clone()), deadline)
+        {
+            self.dispatch.sender.wait(&done, deadline)
+        } else {
+            // the hub is no longer running, or its queue stayed full until the deadline
+            self.dispatch.pending.load(Ordering::SeqCst) == 0
+        };
+        if !flushed {
+            self.warn_pending();
+        }
     }
 }
 
+/// The outcome of processing a triggered alert, as reported by [ProcessingReceipt::wait_processed]
+#[derive(Clone, Debug)]
+pub enum DeliveryOutcome {
+    /// The alert was successfully sent by the backend
+    Sent,
+    /// The alert was not sent, as an alert with the same dedup key was sent recently
+    Suppressed,
+    /// The alert was dropped by a [middleware](crate::middleware) before reaching the backend
+    Filtered,
+    /// The alert was dropped, as no hub is configured (or the configured hub was already shut down)
+    NoHub,
+    /// The backend failed sending the alert
+    Failed(Arc<anyhow::Error>),
+}
+
+/// A receipt for a triggered alert, which can be used to wait for the alert to be processed by the backend.
+///
+/// Waiting can be done either by blocking, using [ProcessingReceipt::wait_processed], or from async code by awaiting
+/// the receipt itself:
+///
+/// ```
+/// # async fn f() {
+/// let outcome = airbag::Alert::builder().title("hello").trigger().await;
+/// # }
+/// ```
 #[derive(Default, Clone)]
 pub struct ProcessingReceipt {
-    cond: Arc<(parking_lot::Mutex<bool>, parking_lot::Condvar)>,
+    outcome: Completion<DeliveryOutcome>,
 }
 
 impl ProcessingReceipt {
-    pub(crate) fn mark_processed(&self) {
-        let mut locked = self.cond.0.lock();
-        *locked = true;
-        self.cond.1.notify_all();
+    pub(crate) fn mark_processed(&self, outcome: DeliveryOutcome) {
+        self.outcome.complete(outcome);
     }
 
-    pub fn wait_processed(&self) {
-        let mut finished = self.cond.0.lock();
-        while !*finished {
-            self.cond.1.wait(&mut finished);
-        }
+    /// Waits for the alert to be processed, returning the outcome of its delivery. Async code should await the
+    /// receipt instead, to avoid blocking the executor
+    pub fn wait_processed(&self) -> DeliveryOutcome {
+        self.outcome.wait()
+    }
+
+    /// Waits up to `timeout` for the alert to be processed, returning `None` if it was not processed in time
+    pub fn wait_processed_timeout(&self, timeout: std::time::Duration) -> Option<DeliveryOutcome> {
+        self.outcome
+            .wait_deadline(Some(std::time::Instant::now() + timeout))
+    }
+}
+
+impl std::future::Future for ProcessingReceipt {
+    type Output = DeliveryOutcome;
+
+    fn poll(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Self::Output> {
+        std::pin::Pin::new(&mut self.outcome).poll(cx)
     }
 }
@@ -0,0 +1,140 @@
+use std::{
+    sync::{atomic::Ordering, Arc},
+    time::Instant,
+};
+
+use parking_lot::Mutex;
+use tokio::task::JoinSet;
+
+use super::{DeliveryOutcome, HubDispatch, HubMessage, HubSender};
+use crate::{backends::AsyncBackend, completion::Completion, dedup::Deduplicator};
+
+/// Spawns a dispatcher task on the current tokio runtime. Unlike the thread-based dispatcher, alerts are delivered by
+/// separate tasks, so a slow delivery does not hold back the ones following it
+pub(super) fn spawn_backend<B: AsyncBackend>(backend: B) -> HubDispatch {
+    let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+    let dispatch = HubDispatch::new(HubSender::Task(sender));
+    let backend = Arc::new(backend);
+    let dedup = Arc::new(Mutex::new(Deduplicator::new(dispatch.dedup_window.clone())));
+    let pending = dispatch.pending.clone();
+
+    tokio::spawn(async move {
+        log::debug!("Backend started...");
+        let mut in_flight = JoinSet::new();
+        loop {
+            // wake up when a dedup window expires, so that summaries of suppressed alerts are sent in time
+            let next_summary_at = dedup.lock().next_summary_at();
+            let msg = match next_summary_at {
+                Some(deadline) => {
+                    match tokio::time::timeout_at(deadline.into(), receiver.recv()).await {
+                        Ok(Some(msg)) => Some(msg),
+                        Ok(None) => break,
+                        Err(_) => None,
+                    }
+                }
+                None => match receiver.recv().await {
+                    Some(msg) => Some(msg),
+                    None => break,
+                },
+            };
+
+            while in_flight.try_join_next().is_some() {}
+
+            let summaries = dedup.lock().take_summaries(Instant::now(), false);
+            for summary in summaries {
+                deliver(&mut in_flight, &backend, &dedup, summary, |_| {});
+            }
+
+            match msg {
+                Some(HubMessage::Alert(alert, receipt, _)) => {
+                    let pending = pending.clone();
+                    deliver(&mut in_flight, &backend, &dedup, alert, move |outcome| {
+                        receipt.mark_processed(outcome);
+                        pending.fetch_sub(1, Ordering::SeqCst);
+                    });
+                }
+                Some(HubMessage::Flush(done)) => {
+                    while in_flight.join_next().await.is_some() {}
+                    done.complete(());
+                }
+                Some(HubMessage::Terminate(done)) => {
+                    log::debug!("Backend received termination signal");
+                    let summaries = dedup.lock().take_summaries(Instant::now(), true);
+                    for summary in summaries {
+                        deliver(&mut in_flight, &backend, &dedup, summary, |_| {});
+                    }
+                    while in_flight.join_next().await.is_some() {}
+                    done.complete(());
+                    break;
+                }
+                None => continue,
+            }
+            log::debug!("Backend waiting for next message...")
+        }
+
+        log::debug!("Backend task terminating")
+    });
+
+    dispatch
+}
+
+fn deliver<B: AsyncBackend>(
+    in_flight: &mut JoinSet<()>,
+    backend: &Arc<B>,
+    dedup: &Arc<Mutex<Deduplicator>>,
+    alert: crate::Alert,
+    on_done: impl FnOnce(DeliveryOutcome) + Send + 'static,
+) {
+    let alert_id = alert.id();
+    let now = Instant::now();
+
+    let dedup_entry = {
+        let mut dedup = dedup.lock();
+        let dedup_entry = dedup.entry(&alert);
+        if dedup.is_duplicate(&dedup_entry, now) {
+            log::debug!("Skipping sending #{alert_id} - same dedup key sent recently");
+            dedup.mark_suppressed(&dedup_entry, alert);
+            drop(dedup);
+            on_done(DeliveryOutcome::Suppressed);
+            return;
+        }
+        // marked as sent up front, so that duplicates arriving while this alert is in flight are suppressed
+        dedup.mark_sent(&dedup_entry, now);
+        dedup_entry
+    };
+
+    let backend = backend.clone();
+    let dedup = dedup.clone();
+    in_flight.spawn(async move {
+        log::debug!("Backend got alert #{alert_id}. Sending...");
+        let outcome = match backend.send(alert).await {
+            Ok(()) => {
+                log::debug!("Alert #{alert_id} sent successfully");
+                DeliveryOutcome::Sent
+            }
+            Err(e) => {
+                log::error!("Failed sending alert: {e:?}");
+                dedup.lock().forget(&dedup_entry);
+                DeliveryOutcome::Failed(Arc::new(e))
+            }
+        };
+        on_done(outcome);
+    });
+}
+
+/// Blocks until the dispatcher completes the given signal. Blocking is only possible outside of a tokio runtime or
+/// within a multi-threaded one, as a current-thread runtime would not be able to drive the dispatcher meanwhile
+pub(super) fn block_on(done: &Completion<()>, deadline: Option<Instant>) -> bool {
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => match handle.runtime_flavor() {
+            tokio::runtime::RuntimeFlavor::CurrentThread => {
+                log::warn!(
+                    "Cannot wait for airbag alerts to be flushed on a current-thread runtime. Use ConfiguredHubGuard::shutdown instead"
+                );
+                false
+            }
+            _ => tokio::task::block_in_place(|| done.wait_deadline(deadline).is_some()),
+        },
+        Err(_) => done.wait_deadline(deadline).is_some(),
+    }
+}
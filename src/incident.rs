@@ -0,0 +1,87 @@
+//! Incidents are sometimes tied to a scope rather than to a single event -- for instance, a service running in a
+//! degraded mode for as long as a fallback is in use. For these cases Airbag provides [IncidentGuard], which triggers
+//! an alert when created and resolves it once the guard is dropped or explicitly closed:
+//!
+//! ```
+//! use airbag::Alert;
+//!
+//! fn run_with_fallback_db() {
+//!     let _incident = Alert::builder()
+//!         .title("Fallback DB in use")
+//!         .dedup_key("fallback-db")
+//!         .trigger_guarded();
+//!
+//!     // ... the incident is resolved when `_incident` goes out of scope
+//! }
+//! ```
+use crate::{
+    alert::{Action, Alert},
+    hub::HubDispatch,
+    ProcessingReceipt,
+};
+
+/// A guard for a triggered incident, resolving it when dropped. Created by
+/// [AlertBuilder::trigger_guarded](crate::alert::AlertBuilder::trigger_guarded)
+#[must_use = "the incident is resolved as soon as the guard is dropped"]
+pub struct IncidentGuard {
+    dedup_key: String,
+    dispatch: Option<HubDispatch>,
+    receipt: ProcessingReceipt,
+    closed: bool,
+}
+
+impl IncidentGuard {
+    pub(crate) fn trigger(alert: Alert) -> Self {
+        let dedup_key = alert
+            .dedup_key()
+            .clone()
+            .expect("guarded alerts must have a dedup key");
+
+        // the dispatch is captured so that the incident gets resolved through the same hub even if the guard is
+        // dropped on a different thread
+        let dispatch = crate::hub::get_backend();
+        let receipt = crate::hub::trigger_on(dispatch.clone(), alert);
+
+        Self {
+            dedup_key,
+            dispatch,
+            receipt,
+            closed: false,
+        }
+    }
+
+    /// Returns the dedup key tying the triggered incident to its resolution
+    pub fn dedup_key(&self) -> &str {
+        &self.dedup_key
+    }
+
+    /// Returns the processing receipt of the triggering alert
+    pub fn trigger_receipt(&self) -> &ProcessingReceipt {
+        &self.receipt
+    }
+
+    /// Resolves the incident, returning the processing receipt of the resolving alert
+    pub fn close(mut self) -> ProcessingReceipt {
+        self.resolve()
+    }
+
+    fn resolve(&mut self) -> ProcessingReceipt {
+        self.closed = true;
+        log::debug!("Resolving guarded incident {:?}", self.dedup_key);
+        crate::hub::trigger_on(
+            self.dispatch.clone(),
+            Alert::builder()
+                .dedup_key(self.dedup_key.clone())
+                .action(Action::Resolve)
+                .build(),
+        )
+    }
+}
+
+impl Drop for IncidentGuard {
+    fn drop(&mut self) {
+        if !self.closed {
+            self.resolve();
+        }
+    }
+}
@@ -5,6 +5,8 @@
 //! * [Middleware](middleware) support, allowing applications to customize emitted alerts before they are being sent
 //! * Supports [shortcuts](result) for handling `Result`s with propagation to alerts
 //! * Catches and reports panics (only when configured globally
+//! * [Guarded incidents](incident) which get resolved automatically once their scope ends
+//! * [Condition monitoring](monitor), turning repeated health observations into a single incident
 //!
 //! # Getting Started
 //! You configure airbag by using the [](configure) or [](configure_thread_local) functions to register either a global or a thread-local Airbag handler respectively.
@@ -21,10 +23,21 @@
 //! <p style="background:rgba(255,181,77,0.16);padding:0.75em;">
 //!  <strong>Note:</strong> when in thread-local mode, Airbag does not catch panics, as panic handlers are always a shared resource in Rust
 //! </p>
+//!
+//! # Async Applications
+//! By default, each configured hub delivers alerts from a dedicated thread, using blocking HTTP requests. Enabling the
+//! `tokio` cargo feature adds `configure_async` and `configure_thread_local_async`, which receive an
+//! `AsyncBackend` implementor and deliver alerts as tasks on the application's tokio runtime instead, allowing
+//! multiple alerts to be in flight concurrently.
 pub mod alert;
 pub mod backends;
+mod completion;
+mod dedup;
+mod http;
 mod hub;
+pub mod incident;
 pub mod middleware;
+pub mod monitor;
 mod panic_handler;
 pub mod prelude;
 pub mod result;
@@ -32,7 +45,10 @@ mod utils;
 
 pub use alert::Alert;
 pub use hub::ConfiguredHubGuard;
-pub use hub::{configure, configure_thread_local, ProcessingReceipt};
+pub use hub::{configure, configure_thread_local, DeliveryOutcome, ProcessingReceipt};
+#[cfg(feature = "tokio")]
+pub use hub::{configure_async, configure_thread_local_async};
+pub use incident::IncidentGuard;
 pub use result::AirbagResult;
 
 pub fn trigger(alert: impl Into<Alert>) -> ProcessingReceipt {
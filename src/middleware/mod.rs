@@ -31,6 +31,12 @@
 
 pub trait Middleware {
     fn process(&self, alert: crate::alert::Alert) -> crate::alert::Alert;
+
+    /// Processes an alert, possibly dropping it. Returning `None` prevents the alert from reaching the backend, in
+    /// which case its receipt reports [DeliveryOutcome::Filtered](crate::DeliveryOutcome::Filtered)
+    fn filter(&self, alert: crate::alert::Alert) -> Option<crate::alert::Alert> {
+        Some(self.process(alert))
+    }
 }
 
 pub use crate::alert::middleware::DedupKeyPrefix;
@@ -60,3 +66,32 @@ where
         (self.f)(alert)
     }
 }
+
+pub struct Filter<F>
+where
+    F: Fn(&crate::alert::Alert) -> bool,
+{
+    f: F,
+}
+
+impl<F> Filter<F>
+where
+    F: Fn(&crate::alert::Alert) -> bool,
+{
+    pub(crate) fn new(f: F) -> Self {
+        Self { f }
+    }
+}
+
+impl<F> Middleware for Filter<F>
+where
+    F: Fn(&crate::alert::Alert) -> bool,
+{
+    fn process(&self, alert: crate::alert::Alert) -> crate::alert::Alert {
+        alert
+    }
+
+    fn filter(&self, alert: crate::alert::Alert) -> Option<crate::alert::Alert> {
+        (self.f)(&alert).then_some(alert)
+    }
+}
@@ -0,0 +1,133 @@
+//! Health checks and similar polling loops tend to observe the same failure over and over again. Reporting every
+//! failed probe as an alert is noisy, and a single flaky probe is usually not worth waking anyone up for.
+//!
+//! [ConditionMonitor] debounces such observations: it only triggers an incident after a configurable number of
+//! consecutive failures (and optionally after failing for a minimal duration), and resolves it once the condition
+//! has recovered for a configurable number of consecutive observations. Both alerts share the monitor's dedup key.
+//!
+//! ```
+//! use airbag::monitor::ConditionMonitor;
+//!
+//! fn probe_database() -> anyhow::Result<()> {
+//!     Ok(())
+//! }
+//!
+//! let mut monitor = ConditionMonitor::builder()
+//!     .dedup_key("database-health")
+//!     .title("Database is unreachable")
+//!     .failure_threshold(3)
+//!     .recovery_threshold(2)
+//!     .build();
+//!
+//! for _ in 0..10 {
+//!     match probe_database() {
+//!         Ok(()) => monitor.ok(),
+//!         Err(e) => monitor.failing(&e),
+//!     };
+//! }
+//! ```
+use std::time::{Duration, Instant};
+
+use crate::{
+    alert::{Action, Alert},
+    ProcessingReceipt,
+};
+
+#[derive(typed_builder::TypedBuilder)]
+pub struct ConditionMonitor {
+    /// The dedup key used for both triggering and resolving the incident
+    #[builder(setter(into))]
+    dedup_key: String,
+
+    /// Overrides the title of triggered alerts, which is otherwise derived from the reported error
+    #[builder(default, setter(strip_option, into))]
+    title: Option<String>,
+
+    /// The number of consecutive failures required before triggering an incident
+    #[builder(default = 1)]
+    failure_threshold: usize,
+
+    /// The minimal duration the condition has to be failing for before triggering an incident. When set, both this
+    /// and the failure threshold have to be met
+    #[builder(default, setter(strip_option))]
+    failure_duration: Option<Duration>,
+
+    /// The number of consecutive successes required before resolving an open incident
+    #[builder(default = 1)]
+    recovery_threshold: usize,
+
+    #[builder(default, setter(skip))]
+    consecutive_failures: usize,
+
+    #[builder(default, setter(skip))]
+    consecutive_successes: usize,
+
+    #[builder(default, setter(skip))]
+    failing_since: Option<Instant>,
+
+    #[builder(default, setter(skip))]
+    incident_open: bool,
+}
+
+impl ConditionMonitor {
+    /// Returns whether an incident is currently open for the monitored condition
+    pub fn is_failing(&self) -> bool {
+        self.incident_open
+    }
+
+    /// Records a successful observation. Returns the receipt of the resolving alert, if this observation resolved an
+    /// open incident
+    pub fn ok(&mut self) -> Option<ProcessingReceipt> {
+        self.consecutive_failures = 0;
+        self.failing_since = None;
+        self.consecutive_successes += 1;
+
+        if self.incident_open && self.consecutive_successes >= self.recovery_threshold {
+            log::debug!("Condition {:?} recovered. Resolving...", self.dedup_key);
+            self.incident_open = false;
+            return Some(crate::hub::trigger(
+                Alert::builder()
+                    .dedup_key(self.dedup_key.clone())
+                    .action(Action::Resolve)
+                    .build(),
+            ));
+        }
+        None
+    }
+
+    /// Records a failed observation. Returns the receipt of the triggered alert, if this observation triggered a new
+    /// incident
+    #[track_caller]
+    pub fn failing<E: std::fmt::Debug + 'static>(
+        &mut self,
+        error: &E,
+    ) -> Option<ProcessingReceipt> {
+        let now = Instant::now();
+        self.consecutive_successes = 0;
+        self.consecutive_failures += 1;
+        let failing_since = *self.failing_since.get_or_insert(now);
+
+        if self.incident_open || self.consecutive_failures < self.failure_threshold {
+            return None;
+        }
+
+        if let Some(failure_duration) = self.failure_duration {
+            if now.duration_since(failing_since) < failure_duration {
+                return None;
+            }
+        }
+
+        log::debug!(
+            "Condition {:?} failed {} consecutive times. Triggering...",
+            self.dedup_key,
+            self.consecutive_failures
+        );
+        self.incident_open = true;
+
+        let mut alert = Alert::build_error_alert(error).dedup_key(self.dedup_key.clone());
+        if let Some(title) = &self.title {
+            alert = alert.title(title.clone());
+        }
+        Some(alert.trigger())
+    }
+}
@@ -72,22 +72,28 @@
 //!
 //!
 pub trait AirbagResult<E>: Sized {
+    #[track_caller]
     fn airbag_drop(self) {
         drop(self.airbag())
     }
 
+    #[track_caller]
     fn airbag_drop_with_dedup_key<S: Into<String>, F: Fn() -> S>(self, dedup_key_factory: F) {
         drop(self.airbag_with_dedup_key(dedup_key_factory))
     }
 
+    #[track_caller]
     fn airbag_with_dedup_key<S: Into<String>, F: Fn() -> S>(self, dedup_key_factory: F) -> Self;
 
+    #[track_caller]
     fn airbag_if<F: Fn(&E) -> bool>(self, f: F) -> Self;
 
+    #[track_caller]
     fn airbag(self) -> Self;
 }
 
 impl<T, E: std::fmt::Debug + 'static> AirbagResult<E> for Result<T, E> {
+    #[track_caller]
     fn airbag(self) -> Self {
         if let Err(e) = &self {
             log::error!("Airbag: handling error {e:?}");
@@ -96,6 +102,7 @@ impl<T, E: std::fmt::Debug + 'static> AirbagResult<E> for Result<T, E> {
         self
     }
 
+    #[track_caller]
     fn airbag_if<F: Fn(&E) -> bool>(self, f: F) -> Self {
         if let Err(e) = &self {
             if f(e) {
@@ -105,6 +112,7 @@ impl<T, E: std::fmt::Debug + 'static> AirbagResult<E> for Result<T, E> {
         self
     }
 
+    #[track_caller]
     fn airbag_with_dedup_key<S: Into<String>, F: Fn() -> S>(self, dedup_key_factory: F) -> Self {
         if let Err(e) = &self {
             crate::trigger(
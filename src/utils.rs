@@ -1,5 +1,3 @@
-use anyhow::Context;
-
 pub(crate) fn sha256(s: &str) -> String {
     use sha2::{Digest, Sha256};
     let mut hasher = Sha256::new();
@@ -7,58 +5,6 @@ pub(crate) fn sha256(s: &str) -> String {
     format!("{:x}", hasher.finalize())
 }
 
-const MAX_RETRY_SLEEP_DURATION: std::time::Duration = std::time::Duration::from_secs(30);
-
-enum RequestError {
-    ClientSideError(anyhow::Error),
-    ServerSideError(anyhow::Error),
-}
-
-pub(crate) fn http_retry<F: Fn(&reqwest::blocking::Client) -> reqwest::blocking::RequestBuilder>(
-    func: F,
-) -> anyhow::Result<()> {
-    let client = reqwest::blocking::Client::new();
-
-    let mut sleep_duration = std::time::Duration::from_millis(500);
-
-    loop {
-        let res = func(&client)
-            .send()
-            .context("Failed sending HTTP request")
-            .map_err(RequestError::ServerSideError)
-            .and_then(|resp| {
-                let status = resp.status();
-                if status.is_success() {
-                    Ok(())
-                } else {
-                    let body = resp
-                        .text()
-                        .ok()
-                        .map(|s| format!(": {s:?}"))
-                        .unwrap_or_default();
-                    let e = anyhow::format_err!("HTTP Error: {status}{body}");
-                    Err(if status.is_client_error() {
-                        RequestError::ClientSideError(e)
-                    } else {
-                        RequestError::ServerSideError(e)
-                    })
-                }
-            });
-
-        match res {
-            Ok(_) => break Ok(()),
-            Err(RequestError::ServerSideError(e)) => {
-                log::error!("Error while sending HTTP request: {e:?}. Going to retry...");
-                std::thread::sleep(sleep_duration);
-                sleep_duration = (sleep_duration * 2).min(MAX_RETRY_SLEEP_DURATION);
-            }
-            Err(RequestError::ClientSideError(e)) => {
-                anyhow::bail!(e)
-            }
-        }
-    }
-}
-
 pub(crate) fn json_set_if_not_present(json: &mut serde_json::Value, path: &[&str], value: &str) {
     if path.is_empty() {
         return;
@@ -81,6 +27,37 @@ pub(crate) fn json_set_if_not_present(json: &mut serde_json::Value, path: &[&str
     }
 }
 
+/// Renders a field value as text, strings being taken as is rather than quoted
+pub(crate) fn field_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        value => value.to_string(),
+    }
+}
+
+/// Builds the plain-text message of a notification, holding the alert's description followed by a `name: value` line
+/// per field, and by the given dedup key
+pub(crate) fn plain_text_message(alert: &crate::Alert, dedup_key: Option<&str>) -> String {
+    let mut message = alert.meta().description.clone().unwrap_or_default();
+    for (name, value) in alert.get_fields().as_object().into_iter().flatten() {
+        message.push_str(&format!("\n{name}: {}", field_text(value)));
+    }
+    if let Some(dedup_key) = dedup_key {
+        message.push_str(&format!("\nDedup key: {dedup_key}"));
+    }
+    message.trim().to_owned()
+}
+
+/// Truncates a string to at most `max_chars` characters, marking truncated strings with a trailing ellipsis
+pub(crate) fn truncate(s: &str, max_chars: usize) -> String {
+    if s.chars().count() <= max_chars {
+        return s.to_owned();
+    }
+    let mut truncated: String = s.chars().take(max_chars.saturating_sub(3)).collect();
+    truncated.push_str("...");
+    truncated
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -113,4 +90,14 @@ mod tests {
 
         assert_eq!(value, json!({"some": {"path": "a"}}));
     }
+
+    #[test]
+    fn test_truncate() {
+        use super::truncate;
+
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("exactly10!", 10), "exactly10!");
+        assert_eq!(truncate("this is too long", 10), "this is...");
+        assert_eq!(truncate("ééééééé", 5), "éé...");
+    }
 }
@@ -0,0 +1,21 @@
+use airbag::{backends::Backend, Alert};
+use std::sync::Arc;
+
+#[derive(Default)]
+pub struct TestBackend {
+    target: Arc<parking_lot::Mutex<Vec<airbag::Alert>>>,
+}
+
+impl TestBackend {
+    pub fn target(&self) -> Arc<parking_lot::Mutex<Vec<airbag::Alert>>> {
+        self.target.clone()
+    }
+}
+
+impl Backend for TestBackend {
+    fn send(&mut self, alert: Alert) -> anyhow::Result<()> {
+        println!("Alerting: {:?}", alert.get_fields());
+        self.target.lock().push(alert);
+        Ok(())
+    }
+}
@@ -0,0 +1,92 @@
+use httpmock::prelude::*;
+use serde_json::{json, Value};
+
+fn alert(req: &HttpMockRequest) -> Value {
+    let body: Value =
+        serde_json::from_slice(req.body.as_deref().unwrap_or_default()).unwrap_or_default();
+    body[0].clone()
+}
+
+fn labels() -> Value {
+    json!({
+        "service": "billing",
+        "alertname": "Disk full",
+        "severity": "warning",
+        "dedup_key": "disk-full",
+    })
+}
+
+fn is_trigger(req: &HttpMockRequest) -> bool {
+    let alert = alert(req);
+    alert["labels"] == labels()
+        && alert["annotations"]
+            == json!({"summary": "Disk full", "description": "Only 3% left", "usage": "97"})
+        && alert["startsAt"].is_string()
+        && alert.get("endsAt").is_none()
+}
+
+fn is_resolve(req: &HttpMockRequest) -> bool {
+    let alert = alert(req);
+    alert["labels"] == labels() && alert["endsAt"].is_string()
+}
+
+#[test]
+fn test_alertmanager_trigger_and_resolve() {
+    let server = MockServer::start();
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::Alertmanager::builder()
+            .base_url(server.url(""))
+            .labels(vec![("service".to_owned(), "billing".to_owned())])
+            .build(),
+    );
+
+    let trigger = server.mock(|when, then| {
+        when.method(POST).path("/api/v2/alerts").matches(is_trigger);
+        then.status(200);
+    });
+    let resolve = server.mock(|when, then| {
+        when.method(POST).path("/api/v2/alerts").matches(is_resolve);
+        then.status(200);
+    });
+
+    let guard = airbag::Alert::builder()
+        .title("Disk full")
+        .description("Only 3% left")
+        .dedup_key("disk-full")
+        .severity(airbag::alert::Severity::Warning)
+        .field("usage", 97)
+        .trigger_guarded();
+    guard.trigger_receipt().wait_processed();
+    guard.close().wait_processed();
+
+    trigger.assert();
+    resolve.assert();
+}
+
+#[test]
+fn test_alertmanager_resolve_after_restart() {
+    let server = MockServer::start();
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::Alertmanager::builder()
+            .base_url(server.url(""))
+            .labels(vec![("service".to_owned(), "billing".to_owned())])
+            .build(),
+    );
+
+    // a backend which never triggered the alert resolves it with the labels of the resolving alert
+    let resolve = server.mock(|when, then| {
+        when.method(POST).path("/api/v2/alerts").matches(is_resolve);
+        then.status(200);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk full")
+            .dedup_key("disk-full")
+            .severity(airbag::alert::Severity::Warning)
+            .action(airbag::alert::Action::Resolve),
+    )
+    .wait_processed();
+
+    resolve.assert();
+}
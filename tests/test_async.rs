@@ -0,0 +1,26 @@
+use airbag::{Alert, DeliveryOutcome};
+use common::TestBackend;
+
+mod common;
+
+#[tokio::test]
+async fn test_await_receipt() {
+    let backend = TestBackend::default();
+    let target = backend.target();
+
+    let _guard = airbag::configure_thread_local(backend);
+
+    let outcome = Alert::builder().title("hello").trigger().await;
+    assert!(matches!(outcome, DeliveryOutcome::Sent));
+    assert_eq!(target.lock().len(), 1);
+}
+
+#[tokio::test]
+async fn test_await_receipt_clones() {
+    let _guard = airbag::configure_thread_local(TestBackend::default());
+
+    let receipt = Alert::builder().title("hello").trigger();
+    let (first, second) = tokio::join!(receipt.clone(), receipt);
+    assert!(matches!(first, DeliveryOutcome::Sent));
+    assert!(matches!(second, DeliveryOutcome::Sent));
+}
@@ -0,0 +1,99 @@
+#![cfg(feature = "tokio")]
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use airbag::{backends::AsyncBackend, Alert, DeliveryOutcome};
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[derive(Default)]
+struct SlowAsyncBackend {
+    target: Arc<parking_lot::Mutex<Vec<Alert>>>,
+}
+
+impl AsyncBackend for SlowAsyncBackend {
+    async fn send(&self, alert: Alert) -> anyhow::Result<()> {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        self.target.lock().push(alert);
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_async_backend_concurrent_delivery() {
+    let backend = SlowAsyncBackend::default();
+    let target = backend.target.clone();
+
+    let guard = airbag::configure_thread_local_async(backend);
+
+    let start = Instant::now();
+    let receipts = (0..5)
+        .map(|i| Alert::builder().title(format!("alert {i}")).trigger())
+        .collect::<Vec<_>>();
+    for receipt in receipts {
+        assert!(matches!(receipt.await, DeliveryOutcome::Sent));
+    }
+    assert!(start.elapsed() < Duration::from_millis(800));
+    assert_eq!(target.lock().len(), 5);
+
+    drop(guard);
+}
+
+#[tokio::test]
+async fn test_async_backend_shutdown() {
+    let backend = SlowAsyncBackend::default();
+    let target = backend.target.clone();
+
+    let guard = airbag::configure_thread_local_async(backend);
+
+    Alert::builder().title("hello").dedup_key("key").trigger();
+    // suppressed while the first alert is still in flight
+    let outcome = Alert::builder()
+        .title("hello")
+        .dedup_key("key")
+        .trigger()
+        .await;
+    assert!(matches!(outcome, DeliveryOutcome::Suppressed));
+
+    guard.shutdown().await;
+
+    let alerts = target.lock();
+    assert_eq!(alerts.len(), 2);
+    assert_eq!(alerts[1].title(), &Some("hello (repeated 2 times)".into()));
+}
+
+#[tokio::test]
+async fn test_async_pagerduty() {
+    let server = MockServer::start_async().await;
+
+    let guard = airbag::configure_thread_local_async(
+        airbag::backends::PagerDuty::builder()
+            .token("token here")
+            .base_url(server.url(""))
+            .build(),
+    );
+
+    let mock = server
+        .mock_async(|when, then| {
+            when.method(POST).path("/v2/enqueue").json_body(json!({
+                "routing_key": "token here",
+                "event_action": "trigger",
+                "payload": {
+                    "severity": "critical",
+                    "source": "airbag",
+                    "summary": "hello"
+                }
+            }));
+            then.status(202);
+        })
+        .await;
+
+    let outcome = Alert::builder().title("hello").trigger().await;
+    assert!(matches!(outcome, DeliveryOutcome::Sent));
+
+    mock.assert_async().await;
+    guard.shutdown().await;
+}
@@ -0,0 +1,89 @@
+mod common;
+use airbag::{alert::Action, backends::CodeOwners, prelude::*, Alert};
+use common::TestBackend;
+
+#[test]
+fn test_codeowners() {
+    let billing = TestBackend::default();
+    let billing_target = billing.target();
+    let payments = TestBackend::default();
+    let payments_target = payments.target();
+    let platform = TestBackend::default();
+    let platform_target = platform.target();
+
+    let _guard = airbag::configure_thread_local(
+        CodeOwners::new()
+            .rules(
+                "
+                # the payments team owns all tests, except for this file which belongs to billing
+                tests/**                  payments
+                tests/test_codeowners.rs  billing
+                ",
+            )
+            .unwrap()
+            .owner("billing", billing)
+            .owner("payments", payments)
+            .owner("platform", platform)
+            .default_owner("platform"),
+    );
+
+    let res = Err::<(), _>(anyhow::format_err!("Invoice failed"));
+    let line = line!() + 1;
+    res.airbag_drop_with_dedup_key(|| "invoice-failed");
+    // alerts without a location are delivered to the default owner
+    airbag::trigger(Alert::builder().title("Deployed")).wait_processed();
+    // resolving alerts are delivered to the owner of the incident
+    airbag::trigger(
+        Alert::builder()
+            .dedup_key("invoice-failed")
+            .action(Action::Resolve),
+    )
+    .wait_processed();
+
+    let billing_alerts = billing_target.lock();
+    assert_eq!(billing_alerts.len(), 2);
+    let location = billing_alerts[0].location().unwrap();
+    assert_eq!(location.file, "tests/test_codeowners.rs");
+    assert_eq!(location.line, line);
+    assert_eq!(billing_alerts[1].action(), Action::Resolve);
+
+    let platform_alerts = platform_target.lock();
+    assert_eq!(platform_alerts.len(), 1);
+    assert_eq!(platform_alerts[0].title().as_deref(), Some("Deployed"));
+
+    assert!(payments_target.lock().is_empty());
+}
+
+#[test]
+fn test_codeowners_unknown_incident() {
+    let billing = TestBackend::default();
+    let billing_target = billing.target();
+    let platform = TestBackend::default();
+    let platform_target = platform.target();
+
+    let _guard = airbag::configure_thread_local(
+        CodeOwners::new()
+            .rule("src/billing/", "billing")
+            .unwrap()
+            .owner("billing", billing)
+            .owner("platform", platform)
+            .default_owner("platform"),
+    );
+
+    // the owner of an incident triggered before a restart is unknown, so every owner resolves it
+    airbag::trigger(
+        Alert::builder()
+            .dedup_key("invoice-failed")
+            .action(Action::Resolve),
+    )
+    .wait_processed();
+
+    assert_eq!(billing_target.lock().len(), 1);
+    assert_eq!(platform_target.lock().len(), 1);
+}
+
+#[test]
+fn test_codeowners_invalid_table() {
+    assert!(CodeOwners::new().rules("src/billing/**").is_err());
+    assert!(CodeOwners::new().rules("src/[billing billing").is_err());
+}
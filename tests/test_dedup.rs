@@ -0,0 +1,84 @@
+use airbag::Alert;
+use common::TestBackend;
+
+mod common;
+
+#[test]
+fn test_dedup_suppression() {
+    let backend = TestBackend::default();
+    let target = backend.target();
+
+    let _guard = airbag::configure_thread_local(backend);
+
+    for _ in 0..3 {
+        Alert::builder()
+            .title("hello")
+            .dedup_key("key")
+            .trigger()
+            .wait_processed();
+    }
+
+    assert_eq!(target.lock().len(), 1);
+}
+
+#[test]
+fn test_dedup_suppression_disabled() {
+    let backend = TestBackend::default();
+    let target = backend.target();
+
+    let _guard = airbag::configure_thread_local(backend).without_dedup_suppression();
+
+    for _ in 0..3 {
+        Alert::builder()
+            .title("hello")
+            .dedup_key("key")
+            .trigger()
+            .wait_processed();
+    }
+
+    assert_eq!(target.lock().len(), 3);
+}
+
+#[test]
+fn test_dedup_window_override() {
+    let backend = TestBackend::default();
+    let target = backend.target();
+
+    let _guard = airbag::configure_thread_local(backend)
+        .with_dedup_window(std::time::Duration::from_secs(3600));
+
+    Alert::builder().dedup_key("key").trigger().wait_processed();
+    Alert::builder()
+        .dedup_key("key")
+        .dedup_window(std::time::Duration::ZERO)
+        .trigger()
+        .wait_processed();
+    Alert::builder().dedup_key("key").trigger().wait_processed();
+
+    assert_eq!(target.lock().len(), 2);
+}
+
+#[test]
+fn test_dedup_occurrence_summary() {
+    let backend = TestBackend::default();
+    let target = backend.target();
+
+    let _guard = airbag::configure_thread_local(backend)
+        .with_dedup_window(std::time::Duration::from_millis(200));
+
+    for _ in 0..5 {
+        Alert::builder()
+            .title("hello")
+            .dedup_key("key")
+            .trigger()
+            .wait_processed();
+    }
+    assert_eq!(target.lock().len(), 1);
+
+    std::thread::sleep(std::time::Duration::from_millis(400));
+
+    let alerts = target.lock();
+    assert_eq!(alerts.len(), 2);
+    assert_eq!(alerts[1].title(), &Some("hello (repeated 5 times)".into()));
+    assert_eq!(alerts[1].get_field("occurrences"), Some(&5.into()));
+}
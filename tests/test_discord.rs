@@ -0,0 +1,134 @@
+use httpmock::prelude::*;
+use serde_json::{json, Value};
+
+#[test]
+fn test_discord() {
+    let server = MockServer::start();
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::Discord::builder()
+            .webhook_url(server.url("/api/webhooks/1234/token"))
+            .username("airbag")
+            .build(),
+    );
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/webhooks/1234/token")
+            .json_body(json!({
+                "username": "airbag",
+                "embeds": [{
+                    "title": format!("{}...", "x".repeat(253)),
+                    "color": 0xecb22e,
+                    "description": "Only 3% left",
+                    "fields": [
+                        {"name": "host", "value": "db-1", "inline": true},
+                        {"name": "usage", "value": "97", "inline": true},
+                    ],
+                    "footer": {"text": "Dedup key: disk-full"},
+                }],
+            }));
+        then.status(204);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("x".repeat(300))
+            .description("Only 3% left")
+            .dedup_key("disk-full")
+            .severity(airbag::alert::Severity::Warning)
+            .field("host", "db-1")
+            .field("usage", 97),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+#[test]
+fn test_discord_empty_fields() {
+    let server = MockServer::start();
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::Discord::builder()
+            .webhook_url(server.url("/api/webhooks/1234/token"))
+            .build(),
+    );
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/webhooks/1234/token")
+            .json_body_partial(
+                json!({
+                    "embeds": [{
+                        "fields": [
+                            {"name": "\u{200b}", "value": "db-1"},
+                            {"name": "note", "value": "\u{200b}"},
+                        ],
+                    }],
+                })
+                .to_string(),
+            );
+        then.status(204);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk full")
+            .field("", "db-1")
+            .field("note", " "),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+fn fits_embed_limits(req: &HttpMockRequest) -> bool {
+    let body: Value =
+        serde_json::from_slice(req.body.as_deref().unwrap_or_default()).unwrap_or_default();
+    let embed = &body["embeds"][0];
+    let length = |value: &Value| value.as_str().map_or(0, |s| s.chars().count());
+
+    let fields = embed["fields"].as_array().cloned().unwrap_or_default();
+    let total = length(&embed["title"])
+        + length(&embed["description"])
+        + length(&embed["footer"]["text"])
+        + fields
+            .iter()
+            .map(|field| length(&field["name"]) + length(&field["value"]))
+            .sum::<usize>();
+
+    // five full fields and a shortened one fit alongside the title, description and footer
+    total <= 6000
+        && fields.len() == 6
+        && fields[5]["value"]
+            .as_str()
+            .is_some_and(|v| v.ends_with("..."))
+        && embed["footer"]["text"] == "Dedup key: disk-full"
+}
+
+#[test]
+fn test_discord_embed_length() {
+    let server = MockServer::start();
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::Discord::builder()
+            .webhook_url(server.url("/api/webhooks/1234/token"))
+            .build(),
+    );
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/webhooks/1234/token")
+            .matches(fits_embed_limits);
+        then.status(204);
+    });
+
+    let mut alert = airbag::Alert::builder()
+        .title("Disk full")
+        .description("Only 3% left")
+        .dedup_key("disk-full");
+    for i in 0..25 {
+        alert = alert.field(format!("field-{i:02}"), "x".repeat(1500));
+    }
+    airbag::trigger(alert).wait_processed();
+
+    mock.assert();
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "email")]
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::TcpListener,
+    sync::mpsc,
+};
+
+use airbag::backends::{email::SmtpSecurity, Email};
+
+/// Starts a minimal SMTP server accepting a single message, returning its port and a receiver for the message's data
+fn smtp_sink() -> (u16, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (sender, receiver) = mpsc::channel();
+
+    std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut reader = BufReader::new(stream.try_clone().unwrap());
+        let mut writer = stream;
+        writer.write_all(b"220 localhost ESMTP sink\r\n").unwrap();
+
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).unwrap() == 0 {
+                break;
+            }
+            let command = line.trim_end().to_uppercase();
+            if command.starts_with("EHLO") || command.starts_with("HELO") {
+                writer.write_all(b"250 localhost\r\n").unwrap();
+            } else if command.starts_with("DATA") {
+                writer.write_all(b"354 go ahead\r\n").unwrap();
+                let mut data = String::new();
+                loop {
+                    line.clear();
+                    reader.read_line(&mut line).unwrap();
+                    if line == ".\r\n" {
+                        break;
+                    }
+                    data.push_str(&line);
+                }
+                sender.send(data).unwrap();
+                writer.write_all(b"250 queued\r\n").unwrap();
+            } else if command.starts_with("QUIT") {
+                writer.write_all(b"221 bye\r\n").unwrap();
+                break;
+            } else {
+                writer.write_all(b"250 ok\r\n").unwrap();
+            }
+        }
+    });
+
+    (port, receiver)
+}
+
+#[test]
+fn test_email() {
+    let (port, messages) = smtp_sink();
+
+    let _guard = airbag::configure_thread_local(
+        Email::builder()
+            .host("127.0.0.1")
+            .port(port)
+            .security(SmtpSecurity::None)
+            .from("Airbag <airbag@example.com>")
+            .to(vec!["oncall@example.com".to_owned()])
+            .build(),
+    );
+
+    let outcome = airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk full")
+            .description("Only 3% left on <db-1>")
+            .dedup_key("disk-full")
+            .severity(airbag::alert::Severity::Critical)
+            .field("host", "db-1"),
+    )
+    .wait_processed();
+    assert!(
+        matches!(outcome, airbag::DeliveryOutcome::Sent),
+        "unexpected outcome: {:?}",
+        outcome
+    );
+
+    let message = messages
+        .recv_timeout(std::time::Duration::from_secs(5))
+        .unwrap();
+    assert!(message.contains("Subject: [CRITICAL] Disk full"));
+    assert!(message.contains("To: oncall@example.com"));
+    assert!(message.contains("Content-Type: text/plain"));
+    assert!(message.contains("Content-Type: text/html"));
+    assert!(message.contains("Dedup key: disk-full"));
+    assert!(message.contains("host: db-1"));
+    assert!(message.contains("&lt;db-1&gt;"));
+}
@@ -0,0 +1,128 @@
+mod common;
+use std::time::{Duration, Instant};
+
+use airbag::{
+    alert::Action,
+    backends::{Backend, Failover},
+    Alert, DeliveryOutcome,
+};
+use common::TestBackend;
+
+struct FailingBackend;
+
+impl Backend for FailingBackend {
+    fn send(&mut self, _alert: Alert) -> anyhow::Result<()> {
+        anyhow::bail!("service unavailable")
+    }
+}
+
+struct HangingBackend;
+
+impl Backend for HangingBackend {
+    fn send(&mut self, _alert: Alert) -> anyhow::Result<()> {
+        std::thread::sleep(Duration::from_secs(2));
+        Ok(())
+    }
+}
+
+#[test]
+fn test_failover_primary_succeeds() {
+    let primary = TestBackend::default();
+    let primary_target = primary.target();
+    let secondary = TestBackend::default();
+    let secondary_target = secondary.target();
+
+    let _guard = airbag::configure_thread_local(
+        Failover::new()
+            .with_backend(primary)
+            .with_backend(secondary),
+    );
+
+    airbag::trigger(Alert::builder().title("Disk full")).wait_processed();
+    assert_eq!(primary_target.lock().len(), 1);
+    assert_eq!(secondary_target.lock().len(), 0);
+}
+
+#[test]
+fn test_failover_on_error() {
+    let secondary = TestBackend::default();
+    let secondary_target = secondary.target();
+
+    let _guard = airbag::configure_thread_local(
+        Failover::new()
+            .with_backend(FailingBackend)
+            .with_backend(secondary),
+    );
+
+    let outcome = airbag::trigger(Alert::builder().title("Disk full")).wait_processed();
+    assert!(
+        matches!(outcome, DeliveryOutcome::Sent),
+        "unexpected outcome: {:?}",
+        outcome
+    );
+    assert_eq!(secondary_target.lock().len(), 1);
+}
+
+#[test]
+fn test_failover_on_timeout() {
+    let secondary = TestBackend::default();
+    let secondary_target = secondary.target();
+
+    let _guard = airbag::configure_thread_local(
+        Failover::new()
+            .with_backend(HangingBackend)
+            .with_backend(secondary)
+            .timeout(Duration::from_millis(200)),
+    );
+
+    let start = Instant::now();
+    airbag::trigger(Alert::builder().title("Disk full")).wait_processed();
+    // the hanging primary is still busy, and is skipped without waiting
+    airbag::trigger(Alert::builder().title("Database down")).wait_processed();
+    assert!(start.elapsed() < Duration::from_secs(1));
+    assert_eq!(secondary_target.lock().len(), 2);
+}
+
+#[test]
+fn test_failover_all_fail() {
+    let _guard = airbag::configure_thread_local(
+        Failover::new()
+            .with_backend(FailingBackend)
+            .with_backend(FailingBackend),
+    );
+
+    match airbag::trigger(Alert::builder().title("Disk full")).wait_processed() {
+        DeliveryOutcome::Failed(e) => assert!(e.to_string().contains("all 2 backends"), "{}", e),
+        other => panic!("unexpected outcome: {:?}", other),
+    }
+}
+
+#[test]
+fn test_failover_resolve_sent_to_all() {
+    let primary = TestBackend::default();
+    let primary_target = primary.target();
+    let secondary = TestBackend::default();
+    let secondary_target = secondary.target();
+
+    let _guard = airbag::configure_thread_local(
+        Failover::new()
+            .with_backend(primary)
+            .with_backend(secondary),
+    );
+
+    airbag::trigger(Alert::builder().title("Disk full").dedup_key("disk-full")).wait_processed();
+    // the incident may have been opened by any backend, so every backend resolves it
+    airbag::trigger(
+        Alert::builder()
+            .dedup_key("disk-full")
+            .action(Action::Resolve),
+    )
+    .wait_processed();
+
+    let actions = |target: &[Alert]| target.iter().map(Alert::action).collect::<Vec<_>>();
+    assert_eq!(
+        actions(&primary_target.lock()),
+        [Action::Trigger, Action::Resolve]
+    );
+    assert_eq!(actions(&secondary_target.lock()), [Action::Resolve]);
+}
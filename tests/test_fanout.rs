@@ -0,0 +1,95 @@
+mod common;
+use std::time::{Duration, Instant};
+
+use airbag::{backends::Backend, backends::FanOut, Alert, DeliveryOutcome};
+use common::TestBackend;
+
+struct FailingBackend;
+
+impl Backend for FailingBackend {
+    fn send(&mut self, _alert: Alert) -> anyhow::Result<()> {
+        anyhow::bail!("service unavailable")
+    }
+}
+
+struct SlowBackend(TestBackend);
+
+impl Backend for SlowBackend {
+    fn send(&mut self, alert: Alert) -> anyhow::Result<()> {
+        std::thread::sleep(Duration::from_millis(300));
+        self.0.send(alert)
+    }
+}
+
+#[test]
+fn test_fanout_sends_to_all() {
+    let first = TestBackend::default();
+    let first_target = first.target();
+    let second = TestBackend::default();
+    let second_target = second.target();
+
+    let _guard =
+        airbag::configure_thread_local(FanOut::new().with_backend(first).with_backend(second));
+
+    let outcome = airbag::trigger(Alert::builder().title("Disk full")).wait_processed();
+    assert!(
+        matches!(outcome, DeliveryOutcome::Sent),
+        "unexpected outcome: {:?}",
+        outcome
+    );
+
+    for target in [first_target, second_target] {
+        let alerts = target.lock();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].title().as_deref(), Some("Disk full"));
+    }
+}
+
+#[test]
+fn test_fanout_aggregates_errors() {
+    let backend = TestBackend::default();
+    let target = backend.target();
+
+    let _guard = airbag::configure_thread_local(
+        FanOut::new()
+            .with_backend(FailingBackend)
+            .with_backend(backend)
+            .parallel(true),
+    );
+
+    match airbag::trigger(Alert::builder().title("Disk full")).wait_processed() {
+        DeliveryOutcome::Failed(e) => {
+            let message = e.to_string();
+            assert!(message.contains("1 of 2 backends"), "{}", message);
+            assert!(
+                message.contains("FailingBackend: service unavailable"),
+                "{}",
+                message
+            );
+        }
+        other => panic!("unexpected outcome: {:?}", other),
+    }
+    // the failure of one child does not prevent delivery through the others
+    assert_eq!(target.lock().len(), 1);
+}
+
+#[test]
+fn test_fanout_parallel() {
+    let first = TestBackend::default();
+    let first_target = first.target();
+    let second = TestBackend::default();
+    let second_target = second.target();
+
+    let _guard = airbag::configure_thread_local(
+        FanOut::new()
+            .with_backend(SlowBackend(first))
+            .with_backend(SlowBackend(second))
+            .parallel(true),
+    );
+
+    let start = Instant::now();
+    airbag::trigger(Alert::builder().title("Disk full")).wait_processed();
+    assert!(start.elapsed() < Duration::from_millis(550));
+    assert_eq!(first_target.lock().len(), 1);
+    assert_eq!(second_target.lock().len(), 1);
+}
@@ -0,0 +1,56 @@
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn test_gotify() {
+    let server = MockServer::start();
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::Gotify::builder()
+            .base_url(server.url(""))
+            .app_token("app-token")
+            .build(),
+    );
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/message")
+            .header("X-Gotify-Key", "app-token")
+            .json_body(json!({
+                "title": "Disk full",
+                "message": "Only 3% left\nhost: db-1",
+                "priority": 5,
+            }));
+        then.status(200);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk full")
+            .description("Only 3% left")
+            .severity(airbag::alert::Severity::Warning)
+            .field("host", "db-1"),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+#[test]
+fn test_gotify_base_url_path() {
+    let server = MockServer::start();
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::Gotify::builder()
+            .base_url(server.url("/gotify/"))
+            .app_token("app-token")
+            .build(),
+    );
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/gotify/message");
+        then.status(200);
+    });
+
+    airbag::trigger(airbag::Alert::builder().title("Disk full")).wait_processed();
+
+    mock.assert();
+}
@@ -0,0 +1,49 @@
+use airbag::{alert::Action, Alert};
+use common::TestBackend;
+
+mod common;
+
+#[test]
+fn test_incident_guard_resolves_on_drop() {
+    let backend = TestBackend::default();
+    let target = backend.target();
+
+    let _guard = airbag::configure_thread_local(backend);
+
+    let incident = Alert::builder()
+        .title("Fallback DB in use")
+        .dedup_key("fallback-db")
+        .trigger_guarded();
+    incident.trigger_receipt().wait_processed();
+
+    assert_eq!(target.lock().len(), 1);
+
+    drop(incident);
+    // make sure the resolve alert got processed as well
+    Alert::builder().title("marker").trigger().wait_processed();
+
+    let alerts = target.lock();
+    assert_eq!(alerts.len(), 3);
+    assert_eq!(alerts[0].action(), Action::Trigger);
+    assert_eq!(alerts[1].action(), Action::Resolve);
+    assert_eq!(alerts[1].dedup_key(), &Some("fallback-db".into()));
+}
+
+#[test]
+fn test_incident_guard_close() {
+    let backend = TestBackend::default();
+    let target = backend.target();
+
+    let _guard = airbag::configure_thread_local(backend);
+
+    let incident = Alert::builder().title("Degraded mode").trigger_guarded();
+    let dedup_key = incident.dedup_key().to_owned();
+
+    incident.close().wait_processed();
+
+    let alerts = target.lock();
+    assert_eq!(alerts.len(), 2);
+    assert_eq!(alerts[0].dedup_key(), &Some(dedup_key.clone()));
+    assert_eq!(alerts[1].action(), Action::Resolve);
+    assert_eq!(alerts[1].dedup_key(), &Some(dedup_key));
+}
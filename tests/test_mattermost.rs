@@ -0,0 +1,104 @@
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn test_mattermost() {
+    let (server, _guard) = mock_mattermost();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/hooks/some-id").json_body(json!({
+            "channel": "alerts",
+            "attachments": [{
+                "fallback": "Disk full",
+                "color": "#e01e5a",
+                "title": "Disk full",
+                "text": "```\nOnly 3% left\n```",
+                "fields": [{"title": "host", "value": "db-1", "short": true}],
+                "footer": "Dedup key: disk-full",
+            }],
+        }));
+        then.status(200);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk full")
+            .description("Only 3% left")
+            .dedup_key("disk-full")
+            .field("host", "db-1"),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+#[test]
+fn test_mattermost_resolve() {
+    let (server, _guard) = mock_mattermost();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/hooks/some-id").json_body(json!({
+            "channel": "alerts",
+            "attachments": [{
+                "fallback": "Resolved: Disk full",
+                "color": "#2eb67d",
+                "title": "Resolved: Disk full",
+                "footer": "Dedup key: disk-full",
+            }],
+        }));
+        then.status(200);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk full")
+            .dedup_key("disk-full")
+            .action(airbag::alert::Action::Resolve),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+#[test]
+fn test_mattermost_escaping_and_truncation() {
+    let (server, _guard) = mock_mattermost();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/hooks/some-id").json_body_partial(
+            json!({
+                "attachments": [{
+                    "text": "```\nfailed at \u{2cb}\u{2cb}\u{2cb}main\u{2cb}\u{2cb}\u{2cb}\n```",
+                    "fields": [{"title": format!("{}...", "n".repeat(97)), "value": "db-1"}],
+                    "footer": format!("Dedup key: {}...", "k".repeat(286)),
+                }],
+            })
+            .to_string(),
+        );
+        then.status(200);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk full")
+            .description("failed at ```main```")
+            .dedup_key("k".repeat(1000))
+            .field("n".repeat(1000), "db-1"),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+fn mock_mattermost() -> (MockServer, airbag::ConfiguredHubGuard) {
+    let server = MockServer::start();
+
+    let guard = airbag::configure_thread_local(
+        airbag::backends::Mattermost::builder()
+            .webhook_url(server.url("/hooks/some-id"))
+            .channel("alerts")
+            .build(),
+    );
+
+    (server, guard)
+}
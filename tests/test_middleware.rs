@@ -1,5 +1,7 @@
-use airbag::{backends::Backend, Alert};
-use std::sync::Arc;
+use airbag::Alert;
+use common::TestBackend;
+
+mod common;
 
 #[test]
 fn test_middleware_title_prefix() {
@@ -46,22 +48,3 @@ fn test_middleware_map() {
         Some("y")
     );
 }
-
-#[derive(Default)]
-pub struct TestBackend {
-    target: Arc<parking_lot::Mutex<Vec<airbag::Alert>>>,
-}
-
-impl TestBackend {
-    pub fn target(&self) -> Arc<parking_lot::Mutex<Vec<airbag::Alert>>> {
-        self.target.clone()
-    }
-}
-
-impl Backend for TestBackend {
-    fn send(&mut self, alert: Alert) -> anyhow::Result<()> {
-        println!("Alerting: {:?}", alert.get_fields());
-        self.target.lock().push(alert);
-        Ok(())
-    }
-}
@@ -0,0 +1,76 @@
+use airbag::{alert::Action, monitor::ConditionMonitor};
+use common::TestBackend;
+
+mod common;
+
+#[test]
+fn test_monitor_failure_threshold() {
+    let backend = TestBackend::default();
+    let target = backend.target();
+
+    let _guard = airbag::configure_thread_local(backend);
+
+    let mut monitor = ConditionMonitor::builder()
+        .dedup_key("health")
+        .title("Service unhealthy")
+        .failure_threshold(3)
+        .recovery_threshold(2)
+        .build();
+
+    let error = anyhow::anyhow!("probe failed");
+
+    assert!(monitor.failing(&error).is_none());
+    assert!(monitor.failing(&error).is_none());
+    assert!(monitor.ok().is_none());
+    assert!(monitor.failing(&error).is_none());
+    assert!(monitor.failing(&error).is_none());
+    monitor
+        .failing(&error)
+        .expect("Third consecutive failure should trigger")
+        .wait_processed();
+    assert!(monitor.is_failing());
+    assert!(monitor.failing(&error).is_none());
+
+    assert!(monitor.ok().is_none());
+    assert!(monitor.failing(&error).is_none());
+    assert!(monitor.ok().is_none());
+    monitor
+        .ok()
+        .expect("Second consecutive success should resolve")
+        .wait_processed();
+    assert!(!monitor.is_failing());
+
+    let alerts = target.lock();
+    assert_eq!(alerts.len(), 2);
+    assert_eq!(alerts[0].action(), Action::Trigger);
+    assert_eq!(alerts[0].title(), &Some("Service unhealthy".into()));
+    assert_eq!(alerts[0].dedup_key(), &Some("health".into()));
+    assert_eq!(alerts[1].action(), Action::Resolve);
+    assert_eq!(alerts[1].dedup_key(), &Some("health".into()));
+}
+
+#[test]
+fn test_monitor_failure_duration() {
+    let backend = TestBackend::default();
+    let target = backend.target();
+
+    let _guard = airbag::configure_thread_local(backend);
+
+    let mut monitor = ConditionMonitor::builder()
+        .dedup_key("health")
+        .failure_duration(std::time::Duration::from_millis(100))
+        .build();
+
+    let error = anyhow::anyhow!("probe failed");
+
+    assert!(monitor.failing(&error).is_none());
+    std::thread::sleep(std::time::Duration::from_millis(150));
+    monitor
+        .failing(&error)
+        .expect("Failing for long enough should trigger")
+        .wait_processed();
+
+    let alerts = target.lock();
+    assert_eq!(alerts.len(), 1);
+    assert_eq!(alerts[0].title(), &Some("probe failed".into()));
+}
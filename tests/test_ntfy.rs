@@ -0,0 +1,34 @@
+use httpmock::prelude::*;
+
+#[test]
+fn test_ntfy() {
+    let server = MockServer::start();
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::Ntfy::builder()
+            .topic_url(server.url("/alerts"))
+            .access_token("tk_token")
+            .build(),
+    );
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/alerts")
+            .query_param("title", "Disk full ⚠")
+            .header("Priority", "5")
+            .header("Authorization", "Bearer tk_token")
+            .body("Only 3% left\nhost: db-1\nDedup key: disk-full");
+        then.status(200);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk full ⚠")
+            .description("Only 3% left")
+            .dedup_key("disk-full")
+            .priority(airbag::alert::Priority::P1)
+            .field("host", "db-1"),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
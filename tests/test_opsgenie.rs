@@ -0,0 +1,97 @@
+use httpmock::prelude::*;
+use serde_json::json;
+
+const API_KEY: &str = "api-key-here";
+
+#[test]
+fn test_opsgenie() {
+    let (server, _guard) = mock_opsgenie();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/v2/alerts")
+            .header("Authorization", format!("GenieKey {API_KEY}"))
+            .json_body(json!({
+                "message": "this is a test summary",
+                "description": "this is a test description",
+                "alias": "some-dedup-key",
+                "priority": "P2",
+                "source": "airbag",
+                "tags": ["airbag", "db", "prod"],
+                "details": {"host": "db-1", "retries": "3"},
+            }));
+        then.status(202);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("this is a test summary")
+            .description("this is a test description")
+            .dedup_key("some-dedup-key")
+            .priority(airbag::alert::Priority::P2)
+            .field("host", "db-1")
+            .field("retries", 3)
+            .field("tags", ["db", "prod"]),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+#[test]
+fn test_opsgenie_close() {
+    let (server, _guard) = mock_opsgenie();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/v2/alerts/some%20dedup%20key/close")
+            .query_param("identifierType", "alias")
+            .header("Authorization", format!("GenieKey {API_KEY}"))
+            .json_body(json!({"source": "airbag"}));
+        then.status(202);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .dedup_key("some dedup key")
+            .action(airbag::alert::Action::Resolve),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+#[test]
+fn test_opsgenie_acknowledge() {
+    let (server, _guard) = mock_opsgenie();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/v2/alerts/some-dedup-key/acknowledge")
+            .query_param("identifierType", "alias");
+        then.status(202);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .dedup_key("some-dedup-key")
+            .action(airbag::alert::Action::Acknowledge),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+fn mock_opsgenie() -> (MockServer, airbag::ConfiguredHubGuard) {
+    let server = MockServer::start();
+
+    let guard = airbag::configure_thread_local(
+        airbag::backends::Opsgenie::builder()
+            .api_key(API_KEY)
+            .base_url(server.url(""))
+            .tags(vec!["airbag".to_owned()])
+            .build(),
+    );
+
+    (server, guard)
+}
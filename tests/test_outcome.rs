@@ -0,0 +1,55 @@
+use airbag::{backends::Backend, Alert, DeliveryOutcome};
+use common::TestBackend;
+
+mod common;
+
+#[test]
+fn test_outcome_sent_and_suppressed() {
+    let _guard = airbag::configure_thread_local(TestBackend::default());
+
+    let outcome = Alert::builder().dedup_key("key").trigger().wait_processed();
+    assert!(matches!(outcome, DeliveryOutcome::Sent));
+
+    let outcome = Alert::builder().dedup_key("key").trigger().wait_processed();
+    assert!(matches!(outcome, DeliveryOutcome::Suppressed));
+}
+
+#[test]
+fn test_outcome_filtered() {
+    let backend = TestBackend::default();
+    let target = backend.target();
+
+    let _guard = airbag::configure_thread_local(backend).filter(|alert| alert.title().is_some());
+
+    let outcome = Alert::builder().trigger().wait_processed();
+    assert!(matches!(outcome, DeliveryOutcome::Filtered));
+
+    let outcome = Alert::builder().title("hello").trigger().wait_processed();
+    assert!(matches!(outcome, DeliveryOutcome::Sent));
+
+    assert_eq!(target.lock().len(), 1);
+}
+
+#[test]
+fn test_outcome_no_hub() {
+    let outcome = Alert::builder().title("hello").trigger().wait_processed();
+    assert!(matches!(outcome, DeliveryOutcome::NoHub));
+}
+
+#[test]
+fn test_outcome_failed() {
+    struct FailingBackend;
+
+    impl Backend for FailingBackend {
+        fn send(&mut self, _alert: Alert) -> anyhow::Result<()> {
+            anyhow::bail!("Service unavailable")
+        }
+    }
+
+    let _guard = airbag::configure_thread_local(FailingBackend);
+
+    match Alert::builder().title("hello").trigger().wait_processed() {
+        DeliveryOutcome::Failed(e) => assert_eq!(e.to_string(), "Service unavailable"),
+        other => panic!("Unexpected outcome: {:?}", other),
+    }
+}
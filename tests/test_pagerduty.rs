@@ -55,6 +55,88 @@ fn test_pagerduty_long_summary() {
     mock.assert();
 }
 
+#[test]
+fn test_pagerduty_resolve() {
+    let (server, _guard) = mock_pd();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/v2/enqueue").json_body(json!({
+            "routing_key": TOKEN,
+            "event_action": "resolve",
+            "dedup_key": "some-dedup-key",
+        }));
+        then.status(202);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("ignored for resolve events")
+            .dedup_key("some-dedup-key")
+            .action(airbag::alert::Action::Resolve),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+#[test]
+fn test_pagerduty_retry_policy() {
+    let server = MockServer::start();
+
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::PagerDuty::builder()
+            .token(TOKEN)
+            .base_url(server.url(""))
+            .retry_policy(
+                airbag::backends::RetryPolicy::builder()
+                    .max_attempts(3)
+                    .base_delay(std::time::Duration::from_millis(10))
+                    .build(),
+            )
+            .build(),
+    );
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/v2/enqueue");
+        then.status(500);
+    });
+
+    let outcome = airbag::trigger(airbag::Alert::builder().title("hello")).wait_processed();
+    assert!(matches!(outcome, airbag::DeliveryOutcome::Failed(_)));
+
+    mock.assert_hits(3);
+}
+
+#[test]
+fn test_pagerduty_rate_limited() {
+    let server = MockServer::start();
+
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::PagerDuty::builder()
+            .token(TOKEN)
+            .base_url(server.url(""))
+            .retry_policy(
+                airbag::backends::RetryPolicy::builder()
+                    .max_attempts(2)
+                    .build(),
+            )
+            .build(),
+    );
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/v2/enqueue");
+        then.status(429).header("Retry-After", "0");
+    });
+
+    let start = std::time::Instant::now();
+    let outcome = airbag::trigger(airbag::Alert::builder().title("hello")).wait_processed();
+    assert!(matches!(outcome, airbag::DeliveryOutcome::Failed(_)));
+    // the Retry-After header takes precedence over the backoff delay
+    assert!(start.elapsed() < std::time::Duration::from_millis(500));
+
+    mock.assert_hits(2);
+}
+
 fn mock_pd() -> (MockServer, airbag::ConfiguredHubGuard) {
     let server = MockServer::start();
 
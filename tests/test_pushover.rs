@@ -0,0 +1,99 @@
+use httpmock::prelude::*;
+
+#[test]
+fn test_pushover_emergency() {
+    let (server, _guard) = mock_pushover();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/1/messages.json")
+            .x_www_form_urlencoded_tuple("token", "app-token")
+            .x_www_form_urlencoded_tuple("user", "user-key")
+            .x_www_form_urlencoded_tuple("title", "Database down")
+            .x_www_form_urlencoded_tuple("message", "Connection refused")
+            .x_www_form_urlencoded_tuple("priority", "2")
+            .x_www_form_urlencoded_tuple("retry", "60")
+            .x_www_form_urlencoded_tuple("expire", "3600")
+            .x_www_form_urlencoded_tuple("tags", "db-down");
+        then.status(200);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Database down")
+            .description("Connection refused")
+            .dedup_key("db-down")
+            .severity(airbag::alert::Severity::Critical),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+#[test]
+fn test_pushover_emergency_limits() {
+    let server = MockServer::start();
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::Pushover::builder()
+            .app_token("app-token")
+            .user_key("user-key")
+            .base_url(server.url(""))
+            .emergency_retry(std::time::Duration::from_secs(5))
+            .emergency_expire(std::time::Duration::from_secs(24 * 60 * 60))
+            .build(),
+    );
+
+    // out of range intervals are clamped to the limits Pushover accepts
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/1/messages.json")
+            .x_www_form_urlencoded_tuple("priority", "2")
+            .x_www_form_urlencoded_tuple("retry", "30")
+            .x_www_form_urlencoded_tuple("expire", "10800");
+        then.status(200);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Database down")
+            .severity(airbag::alert::Severity::Critical),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+#[test]
+fn test_pushover_resolve() {
+    let (server, _guard) = mock_pushover();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/1/receipts/cancel_by_tag/db-down.json")
+            .x_www_form_urlencoded_tuple("token", "app-token");
+        then.status(200);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .dedup_key("db-down")
+            .action(airbag::alert::Action::Resolve),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+fn mock_pushover() -> (MockServer, airbag::ConfiguredHubGuard) {
+    let server = MockServer::start();
+
+    let guard = airbag::configure_thread_local(
+        airbag::backends::Pushover::builder()
+            .app_token("app-token")
+            .user_key("user-key")
+            .base_url(server.url(""))
+            .build(),
+    );
+
+    (server, guard)
+}
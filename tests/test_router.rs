@@ -0,0 +1,125 @@
+mod common;
+use airbag::{
+    alert::{Priority, Severity},
+    backends::{router::Rule, Router},
+    prelude::*,
+    Alert, DeliveryOutcome,
+};
+use common::TestBackend;
+
+#[test]
+fn test_router_first_match() {
+    let pager = TestBackend::default();
+    let pager_target = pager.target();
+    let chat = TestBackend::default();
+    let chat_target = chat.target();
+    let log = TestBackend::default();
+    let log_target = log.target();
+
+    let _guard = airbag::configure_thread_local(
+        Router::new()
+            .route(Rule::severity_at_least(Severity::Error), pager)
+            .route(Rule::severity(Severity::Warning), chat)
+            .fallback(log),
+    );
+
+    airbag::trigger(
+        Alert::builder()
+            .title("Database down")
+            .severity(Severity::Critical),
+    )
+    .wait_processed();
+    airbag::trigger(
+        Alert::builder()
+            .title("Disk almost full")
+            .priority(Priority::P3),
+    )
+    .wait_processed();
+    airbag::trigger(Alert::builder().title("Deployed").severity(Severity::Info)).wait_processed();
+    // alerts without a severity are considered errors
+    airbag::trigger(Alert::builder().title("Unexpected error")).wait_processed();
+
+    let titles = |target: &std::sync::Arc<parking_lot::Mutex<Vec<Alert>>>| {
+        target
+            .lock()
+            .iter()
+            .map(|alert| alert.title().clone().unwrap())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(titles(&pager_target), ["Database down", "Unexpected error"]);
+    assert_eq!(titles(&chat_target), ["Disk almost full"]);
+    assert_eq!(titles(&log_target), ["Deployed"]);
+}
+
+#[test]
+fn test_router_all_matches() {
+    let panics = TestBackend::default();
+    let panics_target = panics.target();
+    let billing = TestBackend::default();
+    let billing_target = billing.target();
+    let fallback = TestBackend::default();
+    let fallback_target = fallback.target();
+
+    let _guard = airbag::configure_thread_local(
+        Router::new()
+            .route(Rule::title_matches("^Panic at").unwrap(), panics)
+            .route(
+                Rule::field_equals("team", "billing").or(Rule::dedup_key_prefix("billing-")),
+                billing,
+            )
+            .fallback(fallback)
+            .all_matches(true),
+    );
+
+    airbag::trigger(
+        Alert::builder()
+            .title("Panic at src/billing.rs:10:5")
+            .field("team", "billing"),
+    )
+    .wait_processed();
+    airbag::trigger(
+        Alert::builder()
+            .title("Invoice failed")
+            .dedup_key("billing-invoice"),
+    )
+    .wait_processed();
+    airbag::trigger(Alert::builder().title("Something else")).wait_processed();
+
+    assert_eq!(panics_target.lock().len(), 1);
+    assert_eq!(billing_target.lock().len(), 2);
+    assert_eq!(fallback_target.lock().len(), 1);
+}
+
+#[test]
+fn test_router_source_file() {
+    let owned = TestBackend::default();
+    let owned_target = owned.target();
+
+    let _guard = airbag::configure_thread_local(
+        Router::new()
+            .route(
+                Rule::source_file_matches("tests/test_router.rs").unwrap(),
+                owned,
+            )
+            .fallback(TestBackend::default()),
+    );
+
+    Err::<(), _>(anyhow::format_err!("Invoice failed")).airbag_drop();
+    airbag::trigger(Alert::builder().title("No location")).wait_processed();
+
+    assert_eq!(owned_target.lock().len(), 1);
+}
+
+#[test]
+fn test_router_no_match() {
+    let _guard = airbag::configure_thread_local(
+        Router::new().route(Rule::field_exists("team"), TestBackend::default()),
+    );
+
+    let outcome = airbag::trigger(Alert::builder().title("Unrouted")).wait_processed();
+    assert!(
+        matches!(outcome, DeliveryOutcome::Failed(_)),
+        "unexpected outcome: {:?}",
+        outcome
+    );
+}
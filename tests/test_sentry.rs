@@ -0,0 +1,88 @@
+use airbag::prelude::*;
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn test_sentry_error() {
+    let (server, _guard) = mock_sentry();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/api/42/store/")
+            .header_exists("X-Sentry-Auth")
+            .matches(|req| {
+                let auth = req
+                    .headers
+                    .as_ref()
+                    .and_then(|headers| {
+                        headers
+                            .iter()
+                            .find(|(name, _)| name.eq_ignore_ascii_case("x-sentry-auth"))
+                    })
+                    .map(|(_, value)| value.clone())
+                    .unwrap_or_default();
+                auth.starts_with("Sentry sentry_version=7") && auth.ends_with("sentry_key=public")
+            })
+            .json_body_partial(
+                json!({
+                    "platform": "rust",
+                    "level": "error",
+                    "environment": "test",
+                    "fingerprint": ["db-errors"],
+                    "exception": {"values": [
+                        {"type": "Error", "value": "connection refused"},
+                        {"type": "anyhow::Error", "value": "Failed saving to DB"},
+                    ]},
+                })
+                .to_string(),
+            );
+        then.status(200).json_body(json!({"id": "some-id"}));
+    });
+
+    Err::<(), _>(anyhow::format_err!("connection refused").context("Failed saving to DB"))
+        .airbag_drop_with_dedup_key(|| "db-errors");
+    airbag::trigger(airbag::Alert::builder().title("flush")).wait_processed();
+
+    mock.assert();
+}
+
+#[test]
+fn test_sentry_message() {
+    let (server, _guard) = mock_sentry();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/api/42/store/").json_body_partial(
+            json!({
+                "level": "warning",
+                "message": {"formatted": "Disk almost full"},
+                "extra": {"host": "db-1", "description": "Only 3% left"},
+            })
+            .to_string(),
+        );
+        then.status(200);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk almost full")
+            .description("Only 3% left")
+            .severity(airbag::alert::Severity::Warning)
+            .field("host", "db-1"),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+fn mock_sentry() -> (MockServer, airbag::ConfiguredHubGuard) {
+    let server = MockServer::start();
+
+    let guard = airbag::configure_thread_local(
+        airbag::backends::Sentry::builder()
+            .dsn(format!("http://public@{}/42", server.address()))
+            .environment("test")
+            .build(),
+    );
+
+    (server, guard)
+}
@@ -0,0 +1,49 @@
+use std::time::{Duration, Instant};
+
+use airbag::{backends::Backend, Alert};
+
+struct SlowBackend(Duration);
+
+impl Backend for SlowBackend {
+    fn send(&mut self, _alert: Alert) -> anyhow::Result<()> {
+        std::thread::sleep(self.0);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_wait_processed_timeout() {
+    let _guard = airbag::configure_thread_local(SlowBackend(Duration::from_millis(300)));
+
+    let receipt = Alert::builder().title("hello").trigger();
+    assert!(receipt
+        .wait_processed_timeout(Duration::from_millis(10))
+        .is_none());
+    assert!(receipt
+        .wait_processed_timeout(Duration::from_secs(5))
+        .is_some());
+}
+
+#[test]
+fn test_flush() {
+    let guard = airbag::configure_thread_local(SlowBackend(Duration::from_millis(100)));
+
+    for _ in 0..3 {
+        Alert::builder().title("hello").trigger();
+    }
+
+    assert!(guard.flush(Duration::from_millis(10)) > 0);
+    assert_eq!(guard.flush(Duration::from_secs(5)), 0);
+}
+
+#[test]
+fn test_shutdown_timeout() {
+    let guard = airbag::configure_thread_local(SlowBackend(Duration::from_secs(3600)))
+        .with_shutdown_timeout(Duration::from_millis(100));
+
+    Alert::builder().title("hello").trigger();
+
+    let start = Instant::now();
+    drop(guard);
+    assert!(start.elapsed() < Duration::from_secs(5));
+}
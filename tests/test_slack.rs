@@ -0,0 +1,145 @@
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn test_slack() {
+    let (server, _guard) = mock_slack();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/services/hook").json_body(json!({
+            "text": "Disk full",
+            "channel": "#alerts",
+            "attachments": [{
+                "color": "#ecb22e",
+                "blocks": [
+                    {"type": "header", "text": {"type": "plain_text", "text": "Disk full"}},
+                    {"type": "section", "text": {"type": "mrkdwn", "text": "```Only 3% left```"}},
+                    {"type": "section", "fields": [
+                        {"type": "mrkdwn", "text": "*host*\ndb-1"},
+                        {"type": "mrkdwn", "text": "*usage*\n97"},
+                    ]},
+                    {"type": "context", "elements": [{"type": "mrkdwn", "text": "Dedup key: `disk-full`"}]},
+                ],
+            }],
+        }));
+        then.status(200).body("ok");
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk full")
+            .description("Only 3% left")
+            .dedup_key("disk-full")
+            .severity(airbag::alert::Severity::Warning)
+            .field("host", "db-1")
+            .field("usage", 97),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+#[test]
+fn test_slack_resolve() {
+    let (server, _guard) = mock_slack();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/services/hook").json_body(json!({
+            "text": "Resolved: Disk full",
+            "channel": "#alerts",
+            "attachments": [{
+                "color": "#2eb67d",
+                "blocks": [
+                    {"type": "header", "text": {"type": "plain_text", "text": "Resolved: Disk full"}},
+                    {"type": "context", "elements": [{"type": "mrkdwn", "text": "Dedup key: `disk-full`"}]},
+                ],
+            }],
+        }));
+        then.status(200).body("ok");
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk full")
+            .dedup_key("disk-full")
+            .action(airbag::alert::Action::Resolve),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+#[test]
+fn test_slack_description_backticks() {
+    let (server, _guard) = mock_slack();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/services/hook").json_body_partial(
+            json!({
+                "attachments": [{
+                    "blocks": [
+                        {"type": "header", "text": {"type": "plain_text", "text": "Query failed"}},
+                        {"type": "section", "text": {"type": "mrkdwn", "text": "```Failed running \u{2cb}\u{2cb}\u{2cb}DROP TABLE\u{2cb}\u{2cb}\u{2cb} on \u{2cb}users\u{2cb}```"}},
+                    ],
+                }],
+            })
+            .to_string(),
+        );
+        then.status(200).body("ok");
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Query failed")
+            .description("Failed running ```DROP TABLE``` on `users`"),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+#[test]
+fn test_slack_mrkdwn_escaping() {
+    let (server, _guard) = mock_slack();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/services/hook").json_body_partial(
+            json!({
+                "attachments": [{
+                    "blocks": [
+                        {"type": "header", "text": {"type": "plain_text", "text": "Query failed"}},
+                        {"type": "section", "text": {"type": "mrkdwn", "text": "```a &lt; b```"}},
+                        {"type": "section", "fields": [{"type": "mrkdwn", "text": "*&lt;owner&gt;*\nbilling &amp; payments"}]},
+                        {"type": "context", "elements": [{"type": "mrkdwn", "text": "Dedup key: `&lt;!channel&gt;`"}]},
+                    ],
+                }],
+            })
+            .to_string(),
+        );
+        then.status(200).body("ok");
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Query failed")
+            .description("a < b")
+            .dedup_key("<!channel>")
+            .field("<owner>", "billing & payments"),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+fn mock_slack() -> (MockServer, airbag::ConfiguredHubGuard) {
+    let server = MockServer::start();
+
+    let guard = airbag::configure_thread_local(
+        airbag::backends::Slack::builder()
+            .webhook_url(server.url("/services/hook"))
+            .channel("#alerts")
+            .build(),
+    );
+
+    (server, guard)
+}
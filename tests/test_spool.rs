@@ -0,0 +1,260 @@
+mod common;
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use airbag::{
+    alert::Severity,
+    backends::{Backend, Router, Spool},
+    Alert, DeliveryOutcome,
+};
+use common::TestBackend;
+
+struct FailingBackend;
+
+impl Backend for FailingBackend {
+    fn send(&mut self, _alert: Alert) -> anyhow::Result<()> {
+        anyhow::bail!("service unavailable")
+    }
+}
+
+/// Fails on alerts titled "Poison", delivering the others
+struct PoisonedBackend(TestBackend);
+
+impl Backend for PoisonedBackend {
+    fn send(&mut self, alert: Alert) -> anyhow::Result<()> {
+        if alert.title().as_deref() == Some("Poison") {
+            anyhow::bail!("service unavailable");
+        }
+        self.0.send(alert)
+    }
+}
+
+/// Blocks on the first alert until released, and then crashes, losing the alerts queued in memory
+struct CrashingBackend(crossbeam::channel::Receiver<()>);
+
+impl Backend for CrashingBackend {
+    fn send(&mut self, _alert: Alert) -> anyhow::Result<()> {
+        let _ = self.0.recv();
+        panic!("backend crashed")
+    }
+}
+
+/// Runs a hub in its own thread, as a thread-local hub can only be configured once per thread
+fn run_hub<B: Backend + Send + 'static>(dir: &Path, backend: B, titles: &'static [&'static str]) {
+    let dir = dir.to_owned();
+    std::thread::spawn(move || {
+        let _guard = airbag::configure_thread_local(Spool::new(&dir, backend));
+        for title in titles {
+            airbag::trigger(Alert::builder().title(*title)).wait_processed();
+        }
+    })
+    .join()
+    .unwrap();
+}
+
+fn spool_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("airbag-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    dir
+}
+
+fn spool_files(dir: &PathBuf, extension: &str) -> usize {
+    std::fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter(|entry| {
+                    entry.as_ref().is_ok_and(|entry| {
+                        entry.path().extension().is_some_and(|ext| ext == extension)
+                    })
+                })
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+fn spooled_files(dir: &PathBuf) -> usize {
+    spool_files(dir, "json")
+}
+
+/// Waits for the hubs using the spool directory to release their locks
+fn wait_unlocked(dir: &PathBuf) {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while spool_files(dir, "lock") > 0 {
+        assert!(Instant::now() < deadline, "spool lock was not released");
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn test_spool_delivered() {
+    let dir = spool_dir("delivered");
+    let backend = TestBackend::default();
+    let target = backend.target();
+
+    let _guard = airbag::configure_thread_local(Spool::new(&dir, backend));
+
+    airbag::trigger(Alert::builder().title("Disk full")).wait_processed();
+    assert_eq!(target.lock().len(), 1);
+    assert_eq!(spooled_files(&dir), 0);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_spool_replay() {
+    let dir = spool_dir("replay");
+
+    // deliver from another thread, as a thread-local hub can only be configured once per thread
+    let failing_dir = dir.clone();
+    std::thread::spawn(move || {
+        let _guard = airbag::configure_thread_local(Spool::new(&failing_dir, FailingBackend));
+        let outcome = airbag::trigger(
+            Alert::builder()
+                .title("Disk full")
+                .dedup_key("disk-full")
+                .severity(Severity::Warning)
+                .field("host", "db-1"),
+        )
+        .wait_processed();
+        assert!(
+            matches!(outcome, DeliveryOutcome::Failed(_)),
+            "unexpected outcome: {:?}",
+            outcome
+        );
+    })
+    .join()
+    .unwrap();
+    assert_eq!(spooled_files(&dir), 1);
+
+    let backend = TestBackend::default();
+    let target = backend.target();
+    let _guard = airbag::configure_thread_local(Spool::new(&dir, backend));
+    // leftovers are replayed before any new alert is sent
+    airbag::trigger(Alert::builder().title("Database down")).wait_processed();
+
+    let alerts = target.lock();
+    assert_eq!(alerts.len(), 2);
+    assert_eq!(alerts[0].title().as_deref(), Some("Disk full"));
+    assert_eq!(alerts[0].dedup_key().as_deref(), Some("disk-full"));
+    assert_eq!(
+        alerts[0].get_field("host"),
+        Some(&serde_json::json!("db-1"))
+    );
+    assert_eq!(alerts[1].title().as_deref(), Some("Database down"));
+    assert_eq!(spooled_files(&dir), 0);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_spool_queued_alerts_survive_crash() {
+    let dir = spool_dir("crash");
+    let (release, released) = crossbeam::channel::bounded(0);
+
+    let crashing_dir = dir.clone();
+    std::thread::spawn(move || {
+        let _guard =
+            airbag::configure_thread_local(Spool::new(&crashing_dir, CrashingBackend(released)))
+                .with_shutdown_timeout(Duration::from_millis(10));
+        airbag::trigger(Alert::builder().title("Disk full"));
+        airbag::trigger(Alert::builder().title("Database down"));
+        // alerts are persisted by the triggering thread, even while the backend is still busy
+        assert_eq!(spooled_files(&crashing_dir), 2);
+    })
+    .join()
+    .unwrap();
+
+    release.send(()).unwrap();
+    wait_unlocked(&dir);
+    assert_eq!(spooled_files(&dir), 2);
+
+    let backend = TestBackend::default();
+    let target = backend.target();
+    let _guard = airbag::configure_thread_local(Spool::new(&dir, backend));
+    airbag::trigger(Alert::builder().title("Deployed")).wait_processed();
+
+    let titles = target
+        .lock()
+        .iter()
+        .map(|alert| alert.title().clone().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(titles, ["Disk full", "Database down", "Deployed"]);
+    assert_eq!(spooled_files(&dir), 0);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_spool_running_hub_not_replayed() {
+    let dir = spool_dir("shared");
+    let (release, released) = crossbeam::channel::bounded(0);
+
+    // a hub sharing the directory, whose backend is still stuck with the queued alerts
+    let stuck_dir = dir.clone();
+    std::thread::spawn(move || {
+        let _guard =
+            airbag::configure_thread_local(Spool::new(&stuck_dir, CrashingBackend(released)))
+                .with_shutdown_timeout(Duration::from_millis(10));
+        airbag::trigger(Alert::builder().title("Disk full"));
+        airbag::trigger(Alert::builder().title("Database down"));
+    })
+    .join()
+    .unwrap();
+
+    let backend = TestBackend::default();
+    let target = backend.target();
+    let running_dir = dir.clone();
+    std::thread::spawn(move || {
+        let _guard = airbag::configure_thread_local(Spool::new(&running_dir, backend));
+        airbag::trigger(Alert::builder().title("Deployed")).wait_processed();
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(target.lock().len(), 1);
+    assert_eq!(spooled_files(&dir), 2);
+
+    release.send(()).unwrap();
+    wait_unlocked(&dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_spool_replay_skips_failures() {
+    let dir = spool_dir("poison");
+    run_hub(&dir, FailingBackend, &["Poison", "Disk full"]);
+    assert_eq!(spooled_files(&dir), 2);
+
+    // an alert failing on every replay does not hold back the ones after it
+    let backend = TestBackend::default();
+    let target = backend.target();
+    run_hub(&dir, PoisonedBackend(backend), &[]);
+    let titles = target
+        .lock()
+        .iter()
+        .map(|alert| alert.title().clone().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(titles, ["Disk full"]);
+    assert_eq!(spooled_files(&dir), 1);
+
+    // and is moved aside once it failed too many times
+    for _ in 0..4 {
+        run_hub(&dir, FailingBackend, &[]);
+    }
+    assert_eq!(spooled_files(&dir), 0);
+    assert_eq!(spool_files(&dir, "failed"), 1);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_spool_permanent_failure_discarded() {
+    let dir = spool_dir("permanent");
+    // a router without routes rejects every alert, which no retry can fix
+    run_hub(&dir, Router::new(), &["Disk full"]);
+    assert_eq!(spooled_files(&dir), 0);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,57 @@
+mod common;
+use std::path::Path;
+
+use airbag::{
+    backends::{Backend, Spool},
+    Alert,
+};
+use common::TestBackend;
+
+struct FailingBackend;
+
+impl Backend for FailingBackend {
+    fn send(&mut self, _alert: Alert) -> anyhow::Result<()> {
+        anyhow::bail!("service unavailable")
+    }
+}
+
+fn spool_files(dir: &Path, extension: &str) -> usize {
+    std::fs::read_dir(dir)
+        .unwrap()
+        .filter(|entry| {
+            entry
+                .as_ref()
+                .is_ok_and(|entry| entry.path().extension().is_some_and(|ext| ext == extension))
+        })
+        .count()
+}
+
+#[test]
+fn test_spool_global_hub_released_on_termination() {
+    let dir = std::env::temp_dir().join(format!("airbag-global-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let guard = airbag::configure(Spool::new(&dir, FailingBackend));
+    airbag::trigger(Alert::builder().title("Disk full")).wait_processed();
+    assert_eq!(spool_files(&dir, "json"), 1);
+    drop(guard);
+
+    // the terminated hub is still installed globally, yet no longer holds the spool
+    assert_eq!(spool_files(&dir, "lock"), 0);
+    assert_eq!(spool_files(&dir, "json"), 1);
+
+    let backend = TestBackend::default();
+    let target = backend.target();
+    let _guard = airbag::configure(Spool::new(&dir, backend));
+    airbag::trigger(Alert::builder().title("Deployed")).wait_processed();
+
+    let titles = target
+        .lock()
+        .iter()
+        .map(|alert| alert.title().clone().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(titles, ["Disk full", "Deployed"]);
+    assert_eq!(spool_files(&dir, "json"), 0);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -32,6 +32,30 @@ fn test_squadcast() {
     mock.assert();
 }
 
+#[test]
+fn test_squadcast_resolve() {
+    let (server, _guard) = mock_sc();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path(format!("/v2/incidents/api/{TOKEN}"))
+            .json_body(json!({
+                "status": "resolve",
+                "event_id": "some-dedup-key",
+            }));
+        then.status(202);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .dedup_key("some-dedup-key")
+            .action(airbag::alert::Action::Resolve),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
 fn mock_sc() -> (MockServer, airbag::ConfiguredHubGuard) {
     let server = MockServer::start();
 
@@ -0,0 +1,137 @@
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn test_teams() {
+    let server = MockServer::start();
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::Teams::builder()
+            .webhook_url(server.url("/workflows/hook"))
+            .build(),
+    );
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/workflows/hook").json_body(json!({
+            "type": "message",
+            "attachments": [{
+                "contentType": "application/vnd.microsoft.card.adaptive",
+                "content": {
+                    "$schema": "http://adaptivecards.io/schemas/adaptive-card.json",
+                    "type": "AdaptiveCard",
+                    "version": "1.4",
+                    "body": [
+                        {"type": "TextBlock", "text": "Disk full", "size": "Large", "weight": "Bolder", "color": "Attention", "wrap": true},
+                        {"type": "TextBlock", "text": "Severity: critical", "isSubtle": true, "spacing": "None"},
+                        {"type": "TextBlock", "text": "Only 3% left", "fontType": "Monospace", "wrap": true},
+                        {"type": "FactSet", "facts": [
+                            {"title": "host", "value": "db-1"},
+                            {"title": "usage", "value": "97"},
+                            {"title": "Dedup key", "value": "disk-full"},
+                        ]},
+                    ],
+                    "msteams": {"width": "Full"},
+                },
+            }],
+        }));
+        then.status(202);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk full")
+            .description("Only 3% left")
+            .dedup_key("disk-full")
+            .priority(airbag::alert::Priority::P1)
+            .field("host", "db-1")
+            .field("usage", 97),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+#[test]
+fn test_teams_truncation() {
+    let server = MockServer::start();
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::Teams::builder()
+            .webhook_url(server.url("/workflows/hook"))
+            .build(),
+    );
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/workflows/hook").json_body_partial(
+            json!({
+                "attachments": [{
+                    "content": {
+                        "body": [
+                            {"type": "TextBlock", "text": "Query failed"},
+                            {"type": "TextBlock", "text": "Severity: error"},
+                            {"type": "TextBlock", "text": format!("{}...", "x".repeat(4997))},
+                            {"type": "FactSet", "facts": [
+                                {"title": "query", "value": format!("{}...", "y".repeat(497))},
+                            ]},
+                        ],
+                    },
+                }],
+            })
+            .to_string(),
+        );
+        then.status(202);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Query failed")
+            .description("x".repeat(100_000))
+            .field("query", "y".repeat(100_000)),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+fn is_within_limits(req: &HttpMockRequest) -> bool {
+    let body = req.body.as_deref().unwrap_or_default();
+    let json: serde_json::Value = serde_json::from_slice(body).unwrap_or_default();
+    let card = &json["attachments"][0]["content"]["body"];
+    let facts = card[3]["facts"].as_array().cloned().unwrap_or_default();
+    body.len() < 28 * 1024
+        && card[0]["text"]
+            .as_str()
+            .is_some_and(|title| title.chars().count() == 256)
+        && card[2]["text"]
+            .as_str()
+            .is_some_and(|text| text.starts_with("ééé"))
+        && !facts.is_empty()
+        && facts.len() <= 50
+        && facts.last() == Some(&json!({"title": "Dedup key", "value": "disk-full"}))
+}
+
+#[test]
+fn test_teams_many_fields() {
+    let server = MockServer::start();
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::Teams::builder()
+            .webhook_url(server.url("/workflows/hook"))
+            .build(),
+    );
+
+    let mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/workflows/hook")
+            .matches(is_within_limits);
+        then.status(202);
+    });
+
+    let mut alert = airbag::Alert::builder()
+        .title("t".repeat(1000))
+        .description("é".repeat(100_000))
+        .dedup_key("disk-full");
+    for index in 0..200 {
+        alert = alert.field(format!("{index}-{}", "n".repeat(200)), "\"".repeat(1000));
+    }
+    airbag::trigger(alert).wait_processed();
+
+    mock.assert();
+}
@@ -0,0 +1,70 @@
+use httpmock::prelude::*;
+use serde_json::json;
+
+const PATH: &str = "/integrations/generic/20131114/alert/api-key/database";
+
+#[test]
+fn test_victorops() {
+    let (server, _guard) = mock_victorops();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path(PATH).json_body(json!({
+            "message_type": "WARNING",
+            "entity_id": "disk-full",
+            "entity_display_name": "Disk full",
+            "state_message": "Only 3% left",
+            "monitoring_tool": "airbag",
+            "host": "db-1",
+        }));
+        then.status(200).json_body(json!({"result": "success"}));
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk full")
+            .description("Only 3% left")
+            .dedup_key("disk-full")
+            .severity(airbag::alert::Severity::Warning)
+            .field("host", "db-1"),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+#[test]
+fn test_victorops_resolve() {
+    let (server, _guard) = mock_victorops();
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path(PATH).json_body(json!({
+            "message_type": "RECOVERY",
+            "entity_id": "disk-full",
+            "monitoring_tool": "airbag",
+        }));
+        then.status(200).json_body(json!({"result": "success"}));
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .dedup_key("disk-full")
+            .action(airbag::alert::Action::Resolve),
+    )
+    .wait_processed();
+
+    mock.assert();
+}
+
+fn mock_victorops() -> (MockServer, airbag::ConfiguredHubGuard) {
+    let server = MockServer::start();
+
+    let guard = airbag::configure_thread_local(
+        airbag::backends::VictorOps::builder()
+            .api_key("api-key")
+            .routing_key("database")
+            .base_url(server.url(""))
+            .build(),
+    );
+
+    (server, guard)
+}
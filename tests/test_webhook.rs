@@ -0,0 +1,94 @@
+use httpmock::prelude::*;
+use serde_json::json;
+
+#[test]
+fn test_webhook_default_body() {
+    let server = MockServer::start();
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::Webhook::builder()
+            .url(server.url("/hook"))
+            .build(),
+    );
+
+    let mock = server.mock(|when, then| {
+        when.method(POST).path("/hook").json_body_partial(
+            json!({
+                "title": "Disk full",
+                "description": null,
+                "dedup_key": "disk-full",
+                "severity": "warning",
+                "action": "trigger",
+                "fields": {"host": "db-1"},
+            })
+            .to_string(),
+        );
+        then.status(200);
+    });
+    let resolve_mock = server.mock(|when, then| {
+        when.method(POST)
+            .path("/hook")
+            .json_body_partial(json!({"dedup_key": "disk-full", "action": "resolve"}).to_string());
+        then.status(200);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk full")
+            .dedup_key("disk-full")
+            .severity(airbag::alert::Severity::Warning)
+            .field("host", "db-1"),
+    )
+    .wait_processed();
+    mock.assert();
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk full")
+            .dedup_key("disk-full")
+            .action(airbag::alert::Action::Resolve),
+    )
+    .wait_processed();
+    resolve_mock.assert();
+}
+
+#[test]
+fn test_webhook_template() {
+    let server = MockServer::start();
+    let _guard = airbag::configure_thread_local(
+        airbag::backends::Webhook::builder()
+            .url(server.url("/hook"))
+            .method("put")
+            .headers(vec![("X-Api-Key".to_owned(), "secret".to_owned())])
+            .body(json!({
+                "summary": "[{{severity}}] {{title}}",
+                "key": "{{dedup_key}}",
+                "state": "{{action}}",
+                "host": "{{fields.host}}",
+            }))
+            .build(),
+    );
+
+    let mock = server.mock(|when, then| {
+        when.method(PUT)
+            .path("/hook")
+            .header("X-Api-Key", "secret")
+            .json_body(json!({
+                "summary": "[warning] Disk full",
+                "key": "disk-full",
+                "state": "trigger",
+                "host": {"name": "db-1"},
+            }));
+        then.status(200);
+    });
+
+    airbag::trigger(
+        airbag::Alert::builder()
+            .title("Disk full")
+            .dedup_key("disk-full")
+            .severity(airbag::alert::Severity::Warning)
+            .field("host", json!({"name": "db-1"})),
+    )
+    .wait_processed();
+
+    mock.assert();
+}